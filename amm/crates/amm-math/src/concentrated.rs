@@ -0,0 +1,172 @@
+use crate::{
+    error::MathError,
+    math::{full_mul, mul_div, to_u64, MAX_FEE, Q64},
+};
+
+// Uniswap v3 style math. Prices are sqrt(1.0001^tick) as Q64.64 fixed point numbers, which
+// keeps them between 2^32 and 2^96 over the allowed tick range.
+
+pub const MIN_TICK: i32 = -443_636; // sqrt price of 2^-32
+pub const MAX_TICK: i32 = 443_636; // sqrt price of 2^32
+
+const SQRT_1_0001: u128 = 18_447_666_387_855_959_850; // sqrt(1.0001) as Q64.64
+const LOW_MASK: u128 = u64::MAX as u128;
+
+// (a * b) >> 64, rounded down
+fn mul_shr_64(a: u128, b: u128) -> Result<u128, MathError> {
+    let (hi, lo) = full_mul(a, b);
+    if hi > LOW_MASK {
+        return Err(MathError::Overflow);
+    }
+    Ok((hi << 64) | (lo >> 64))
+}
+
+pub fn sqrt_price_at_tick(tick: i32) -> Result<u128, MathError> {
+    if !(MIN_TICK..=MAX_TICK).contains(&tick) {
+        return Err(MathError::InvalidTick);
+    }
+
+    // sqrt(1.0001)^|tick| by square and multiply
+    let mut bits = tick.unsigned_abs();
+    let mut power = SQRT_1_0001;
+    let mut ratio = Q64;
+    while bits != 0 {
+        if bits & 1 == 1 {
+            ratio = mul_shr_64(ratio, power)?;
+        }
+        bits >>= 1;
+        if bits != 0 {
+            power = mul_shr_64(power, power)?;
+        }
+    }
+
+    match tick < 0 {
+        true => Ok(u128::MAX / ratio),
+        false => Ok(ratio),
+    }
+}
+
+// Greatest tick in [lower, upper] whose price is at or below `sqrt_price`
+pub fn tick_at_sqrt_price(sqrt_price: u128, lower: i32, upper: i32) -> Result<i32, MathError> {
+    let (mut lower, mut upper) = (lower.max(MIN_TICK), upper.min(MAX_TICK));
+    while lower < upper {
+        let mid = lower + (upper - lower + 1) / 2;
+        match sqrt_price_at_tick(mid)? <= sqrt_price {
+            true => lower = mid,
+            false => upper = mid - 1,
+        }
+    }
+    Ok(lower)
+}
+
+// Token x worth `liquidity` between two prices: L * (b - a) / (a * b)
+pub fn amount_x_delta(price_a: u128, price_b: u128, liquidity: u128, round_up: bool) -> Result<u128, MathError> {
+    let (lower, upper) = (price_a.min(price_b), price_a.max(price_b));
+    let amount = mul_div(liquidity, upper - lower, upper, round_up)?;
+    mul_div(amount, Q64, lower, round_up)
+}
+
+// Token y worth `liquidity` between two prices: L * (b - a)
+pub fn amount_y_delta(price_a: u128, price_b: u128, liquidity: u128, round_up: bool) -> Result<u128, MathError> {
+    let (lower, upper) = (price_a.min(price_b), price_a.max(price_b));
+    mul_div(liquidity, upper - lower, Q64, round_up)
+}
+
+// Price after adding `amount` of token x, rounded up so the pool never gives out too much
+fn next_sqrt_price_from_x(sqrt_price: u128, liquidity: u128, amount: u128) -> Result<u128, MathError> {
+    let product = mul_div(amount, sqrt_price, Q64, false)?;
+    let denominator = liquidity.checked_add(product).ok_or(MathError::Overflow)?;
+    mul_div(liquidity, sqrt_price, denominator, true)
+}
+
+// Price after adding `amount` of token y, rounded down for the same reason
+fn next_sqrt_price_from_y(sqrt_price: u128, liquidity: u128, amount: u128) -> Result<u128, MathError> {
+    let delta = mul_div(amount, Q64, liquidity, false)?;
+    sqrt_price.checked_add(delta).ok_or(MathError::Overflow)
+}
+
+pub struct SwapStep {
+    pub sqrt_price: u128, // price at the end of the step
+    pub amount_in: u64, // input used by the step, fee excluded
+    pub amount_out: u64,
+    pub fee: u64,
+}
+
+// Swaps at most `amount_remaining` of input inside a range of constant liquidity, stopping
+// at `sqrt_price_target` if the input is enough to get there
+pub fn compute_swap_step(
+    sqrt_price: u128,
+    sqrt_price_target: u128,
+    liquidity: u128,
+    amount_remaining: u64,
+    fee: u16,
+) -> Result<SwapStep, MathError> {
+    if fee >= MAX_FEE {
+        return Err(MathError::InvalidFeeAmount);
+    }
+
+    let x_to_y = sqrt_price_target < sqrt_price;
+    let amount_less_fee = amount_remaining as u128 * (MAX_FEE - fee) as u128 / MAX_FEE as u128;
+
+    let amount_to_target = match x_to_y {
+        true => amount_x_delta(sqrt_price_target, sqrt_price, liquidity, true)?,
+        false => amount_y_delta(sqrt_price, sqrt_price_target, liquidity, true)?,
+    };
+
+    let reached = amount_less_fee >= amount_to_target;
+    let next = match (reached, x_to_y) {
+        (true, _) => sqrt_price_target,
+        (false, true) => next_sqrt_price_from_x(sqrt_price, liquidity, amount_less_fee)?,
+        (false, false) => next_sqrt_price_from_y(sqrt_price, liquidity, amount_less_fee)?,
+    };
+
+    let (amount_in, amount_out) = match x_to_y {
+        true => (
+            amount_x_delta(next, sqrt_price, liquidity, true)?,
+            amount_y_delta(next, sqrt_price, liquidity, false)?,
+        ),
+        false => (
+            amount_y_delta(sqrt_price, next, liquidity, true)?,
+            amount_x_delta(sqrt_price, next, liquidity, false)?,
+        ),
+    };
+    let amount_in = to_u64(amount_in)?;
+    let amount_out = to_u64(amount_out)?;
+
+    // Whatever is left of the input when the step stops short is all fee
+    let fee = match reached {
+        true => (amount_in as u128 * fee as u128).div_ceil((MAX_FEE - fee) as u128) as u64,
+        false => amount_remaining.saturating_sub(amount_in),
+    };
+
+    Ok(SwapStep {
+        sqrt_price: next,
+        amount_in,
+        amount_out,
+        fee,
+    })
+}
+
+// Token amounts backing `liquidity` in [tick_lower, tick_upper) with the pool at `sqrt_price`
+pub fn liquidity_amounts(
+    sqrt_price: u128,
+    tick_current: i32,
+    tick_lower: i32,
+    tick_upper: i32,
+    liquidity: u128,
+    round_up: bool,
+) -> Result<(u64, u64), MathError> {
+    let price_lower = sqrt_price_at_tick(tick_lower)?;
+    let price_upper = sqrt_price_at_tick(tick_upper)?;
+
+    let (x, y) = match tick_current {
+        t if t < tick_lower => (amount_x_delta(price_lower, price_upper, liquidity, round_up)?, 0),
+        t if t < tick_upper => (
+            amount_x_delta(sqrt_price, price_upper, liquidity, round_up)?,
+            amount_y_delta(price_lower, sqrt_price, liquidity, round_up)?,
+        ),
+        _ => (0, amount_y_delta(price_lower, price_upper, liquidity, round_up)?),
+    };
+
+    Ok((to_u64(x)?, to_u64(y)?))
+}
@@ -0,0 +1,51 @@
+use crate::{
+    error::MathError,
+    math::{mul_div, to_u64, MAX_FEE},
+    quote::{with_fee, SwapQuote},
+};
+
+// x * y = k, with the fee taken from the input and left in the pool
+
+pub fn swap(reserve_in: u64, reserve_out: u64, fee: u16, amount_in: u64) -> Result<SwapQuote, MathError> {
+    if fee >= MAX_FEE {
+        return Err(MathError::InvalidFeeAmount);
+    }
+    if reserve_in == 0 || reserve_out == 0 {
+        return Err(MathError::InsufficientLiquidity);
+    }
+
+    let fee_amount = (amount_in as u128 * fee as u128 / MAX_FEE as u128) as u64;
+    let in_after_fee = (amount_in - fee_amount) as u128;
+
+    // reserve_out * in / (reserve_in + in), rounded down so k never shrinks
+    let amount_out = mul_div(
+        reserve_out as u128,
+        in_after_fee,
+        reserve_in as u128 + in_after_fee,
+        false,
+    )?;
+
+    Ok(SwapQuote {
+        amount_in,
+        amount_out: to_u64(amount_out)?,
+        fee: fee_amount,
+    })
+}
+
+// Amount that has to reach the input vault for the curve to release `amount_out`, and the
+// swap fee included in it. Rounds up so the invariant never shrinks.
+pub fn swap_exact_out(reserve_in: u64, reserve_out: u64, fee: u16, amount_out: u64) -> Result<SwapQuote, MathError> {
+    if reserve_in == 0 || amount_out >= reserve_out {
+        return Err(MathError::InsufficientLiquidity);
+    }
+
+    // (reserve_in + in_after_fee) * (reserve_out - amount_out) >= reserve_in * reserve_out
+    let in_after_fee = mul_div(
+        reserve_in as u128,
+        amount_out as u128,
+        (reserve_out - amount_out) as u128,
+        true,
+    )?;
+
+    with_fee(in_after_fee, amount_out, fee)
+}
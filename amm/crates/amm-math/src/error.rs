@@ -0,0 +1,23 @@
+use core::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MathError {
+    Overflow,
+    Underflow,
+    InsufficientLiquidity,
+    InvalidFeeAmount,
+    InvalidTick,
+}
+
+impl fmt::Display for MathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            MathError::Overflow => "Overflow detected",
+            MathError::Underflow => "Underflow detected",
+            MathError::InsufficientLiquidity => "Insufficient liquidity",
+            MathError::InvalidFeeAmount => "Invalid fee amount",
+            MathError::InvalidTick => "Tick out of range",
+        };
+        f.write_str(message)
+    }
+}
@@ -0,0 +1,15 @@
+#![no_std]
+
+// The amm program does its swap and liquidity math through this crate, so front-ends and bots
+// that quote with it get the same numbers as the program, rounding included.
+
+pub mod concentrated;
+pub mod constant_product;
+pub mod error;
+pub mod math;
+pub mod quote;
+pub mod stable_swap;
+
+pub use error::MathError;
+pub use math::{full_mul, mul_div, sqrt, MAX_FEE, Q64};
+pub use quote::*;
@@ -0,0 +1,75 @@
+use crate::error::MathError;
+
+pub const MAX_FEE: u16 = 10_000; // fees are in basis points
+pub const Q64: u128 = 1 << 64; // one, as a Q64.64 fixed point number
+
+const LOW_MASK: u128 = u64::MAX as u128;
+
+// Full 256 bit product of two u128, as (high, low) halves
+pub fn full_mul(a: u128, b: u128) -> (u128, u128) {
+    let (a1, a0) = (a >> 64, a & LOW_MASK);
+    let (b1, b0) = (b >> 64, b & LOW_MASK);
+
+    let low = a0 * b0;
+    let cross_a = a1 * b0;
+    let cross_b = a0 * b1;
+    let mid = (low >> 64) + (cross_a & LOW_MASK) + (cross_b & LOW_MASK);
+
+    let lo = (low & LOW_MASK) | (mid << 64);
+    let hi = a1 * b1 + (cross_a >> 64) + (cross_b >> 64) + (mid >> 64);
+    (hi, lo)
+}
+
+// a * b / denominator without intermediate overflow
+pub fn mul_div(a: u128, b: u128, denominator: u128, round_up: bool) -> Result<u128, MathError> {
+    if denominator == 0 {
+        return Err(MathError::Overflow);
+    }
+
+    let (hi, lo) = full_mul(a, b);
+    if hi >= denominator {
+        return Err(MathError::Overflow);
+    }
+
+    let (quotient, remainder) = match hi {
+        0 => (lo / denominator, lo % denominator),
+        _ => {
+            // Schoolbook long division of the 256 bit product, one bit at a time
+            let (mut quotient, mut remainder) = (0u128, hi);
+            for i in (0..128).rev() {
+                let carry = remainder >> 127;
+                remainder = (remainder << 1) | ((lo >> i) & 1);
+                quotient <<= 1;
+                if carry == 1 || remainder >= denominator {
+                    remainder = remainder.wrapping_sub(denominator);
+                    quotient |= 1;
+                }
+            }
+            (quotient, remainder)
+        },
+    };
+
+    match round_up && remainder != 0 {
+        true => quotient.checked_add(1).ok_or(MathError::Overflow),
+        false => Ok(quotient),
+    }
+}
+
+// Integer square root, rounded down
+pub fn sqrt(value: u128) -> u128 {
+    if value < 2 {
+        return value;
+    }
+
+    let mut x = value;
+    let mut y = value / 2 + value % 2;
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
+
+pub(crate) fn to_u64(value: u128) -> Result<u64, MathError> {
+    u64::try_from(value).map_err(|_| MathError::Overflow)
+}
@@ -0,0 +1,205 @@
+use crate::{
+    constant_product,
+    error::MathError,
+    math::{mul_div, sqrt, to_u64, MAX_FEE, Q64},
+    stable_swap,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Curve {
+    ConstantProduct, // x * y = k
+    StableSwap { amp: u64 }, // Curve's invariant, with A already ramped to the current time
+}
+
+// What the program sees of a pool when it quotes: the reserves are the vault balances minus
+// the protocol fees, i.e. what the LPs own
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pool {
+    pub curve: Curve,
+    pub fee: u16, // swap fee in basis points
+    pub reserve_x: u64,
+    pub reserve_y: u64,
+    pub supply: u64, // LP tokens in circulation
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SwapQuote {
+    pub amount_in: u64, // to reach the input vault, fee included
+    pub amount_out: u64, // to leave the output vault
+    pub fee: u64, // part of amount_in kept as swap fee
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Amounts {
+    pub x: u64,
+    pub y: u64,
+}
+
+impl Pool {
+
+    // (reserve_in, reserve_out) when selling token x (or y)
+    pub fn reserves(&self, is_x: bool) -> (u64, u64) {
+        match is_x {
+            true => (self.reserve_x, self.reserve_y),
+            false => (self.reserve_y, self.reserve_x),
+        }
+    }
+
+    // Same pool with other reserves
+    pub fn with_reserves(&self, reserve_x: u64, reserve_y: u64) -> Pool {
+        Pool { reserve_x, reserve_y, ..*self }
+    }
+}
+
+// Swaps `amount_in` of token x (or y) against the pool
+pub fn quote_swap(pool: &Pool, is_x: bool, amount_in: u64) -> Result<SwapQuote, MathError> {
+    let (reserve_in, reserve_out) = pool.reserves(is_x);
+    match pool.curve {
+        Curve::ConstantProduct => constant_product::swap(reserve_in, reserve_out, pool.fee, amount_in),
+        Curve::StableSwap { amp } => stable_swap::swap(amp, reserve_in, reserve_out, pool.fee, amount_in),
+    }
+}
+
+// Amount of token x (or y) the vault needs to receive to release exactly `amount_out`
+pub fn quote_swap_exact_out(pool: &Pool, is_x: bool, amount_out: u64) -> Result<SwapQuote, MathError> {
+    let (reserve_in, reserve_out) = pool.reserves(is_x);
+    match pool.curve {
+        Curve::ConstantProduct => constant_product::swap_exact_out(reserve_in, reserve_out, pool.fee, amount_out),
+        Curve::StableSwap { amp } => stable_swap::swap_exact_out(amp, reserve_in, reserve_out, pool.fee, amount_out),
+    }
+}
+
+// Token amounts backing `lp_amount` new LP tokens. Balanced deposits keep either invariant in
+// proportion to the supply, rounding up in the pool's favour.
+pub fn quote_deposit(pool: &Pool, lp_amount: u64) -> Result<Amounts, MathError> {
+    if pool.supply == 0 {
+        return Err(MathError::InsufficientLiquidity);
+    }
+
+    let share = |reserve: u64| -> Result<u64, MathError> {
+        to_u64(mul_div(reserve as u128, lp_amount as u128, pool.supply as u128, true)?)
+    };
+    Ok(Amounts { x: share(pool.reserve_x)?, y: share(pool.reserve_y)? })
+}
+
+// Token amounts released by burning `lp_amount` LP tokens, rounded down
+pub fn quote_withdraw(pool: &Pool, lp_amount: u64) -> Result<Amounts, MathError> {
+    if lp_amount > pool.supply {
+        return Err(MathError::InsufficientLiquidity);
+    }
+
+    let share = |reserve: u64| -> Result<u64, MathError> {
+        to_u64(mul_div(reserve as u128, lp_amount as u128, pool.supply as u128, false)?)
+    };
+    Ok(Amounts { x: share(pool.reserve_x)?, y: share(pool.reserve_y)? })
+}
+
+// LP tokens backing a deposit of `amount` of only token x (or y), and the swap fee charged on
+// the part of it that is implicitly swapped to match the pool ratio
+pub fn quote_deposit_single(pool: &Pool, is_x: bool, amount: u64) -> Result<(u64, u64), MathError> {
+    if pool.supply == 0 {
+        return Err(MathError::InsufficientLiquidity);
+    }
+
+    let invariant_before = invariant(pool)?;
+    if invariant_before == 0 {
+        return Err(MathError::InsufficientLiquidity);
+    }
+
+    let (reserve_in, _) = pool.reserves(is_x);
+    let after = |amount_in: u64| -> Result<Pool, MathError> {
+        let balance_in = reserve_in.checked_add(amount_in).ok_or(MathError::Overflow)?;
+        Ok(match is_x {
+            true => pool.with_reserves(balance_in, pool.reserve_y),
+            false => pool.with_reserves(pool.reserve_x, balance_in),
+        })
+    };
+
+    // What a balanced deposit reaching the same invariant would have put in, the rest of
+    // `amount` is what gets swapped
+    let invariant_after = invariant(&after(amount)?)?;
+    let balanced_in = mul_div(reserve_in as u128, invariant_after, invariant_before, false)?;
+    let swapped = (reserve_in as u128 + amount as u128).saturating_sub(balanced_in);
+    let fee = to_u64((swapped * pool.fee as u128).div_ceil(MAX_FEE as u128))?;
+
+    // Both invariants are only known to the unit, so the growth is taken from one unit above
+    // the one before to one unit below the one after so the LPs never lose to the rounding
+    let invariant_before = invariant_before + 1;
+    let invariant_after = invariant(&after(amount - fee)?)?.saturating_sub(1);
+    let lp_amount = mul_div(
+        pool.supply as u128,
+        invariant_after.saturating_sub(invariant_before),
+        invariant_before,
+        false,
+    )?;
+
+    Ok((to_u64(lp_amount)?, fee))
+}
+
+// Value of the pool invariant that grows linearly with the LP supply, sqrt(x * y) for the
+// constant product and D for the stable swap
+pub fn invariant(pool: &Pool) -> Result<u128, MathError> {
+    match pool.curve {
+        Curve::ConstantProduct => Ok(sqrt(pool.reserve_x as u128 * pool.reserve_y as u128)),
+        Curve::StableSwap { amp } => stable_swap::compute_d(amp, pool.reserve_x, pool.reserve_y),
+    }
+}
+
+// Marginal price of token x (or y) in the other token before fees, as Q64.64
+pub fn spot_price(pool: &Pool, is_x: bool) -> Result<u128, MathError> {
+    let (reserve_in, reserve_out) = pool.reserves(is_x);
+    if reserve_in == 0 || reserve_out == 0 {
+        return Err(MathError::InsufficientLiquidity);
+    }
+
+    let ratio = mul_div(reserve_out as u128, Q64, reserve_in as u128, false)?;
+    match pool.curve {
+        Curve::ConstantProduct => Ok(ratio),
+        Curve::StableSwap { amp } => {
+            // -dy/dx on the invariant: y * (Ann * x + D_P) / (x * (Ann * y + D_P))
+            let d = stable_swap::compute_d(amp, pool.reserve_x, pool.reserve_y)?;
+            let d_p = stable_swap::d_product(d, reserve_in as u128, reserve_out as u128)?;
+            let ann = amp as u128 * 4;
+            mul_div(
+                ratio,
+                ann * reserve_in as u128 + d_p,
+                ann * reserve_out as u128 + d_p,
+                false,
+            )
+        },
+    }
+}
+
+// How much worse than the spot price a swap of `amount_in` executes, in basis points. The
+// swap fee is left out, this is only the price moving along the curve.
+pub fn price_impact(pool: &Pool, is_x: bool, amount_in: u64) -> Result<u64, MathError> {
+    let quote = quote_swap(pool, is_x, amount_in)?;
+    let spot = spot_price(pool, is_x)?;
+
+    let at_spot = mul_div((quote.amount_in - quote.fee) as u128, spot, Q64, false)?;
+    if at_spot == 0 {
+        return Ok(0);
+    }
+
+    let shortfall = at_spot.saturating_sub(quote.amount_out as u128);
+    to_u64(mul_div(shortfall, MAX_FEE as u128, at_spot, true)?)
+}
+
+// Grosses `in_after_fee` up by the swap fee taken from the input
+pub(crate) fn with_fee(in_after_fee: u128, amount_out: u64, fee: u16) -> Result<SwapQuote, MathError> {
+    if fee >= MAX_FEE {
+        return Err(MathError::InvalidFeeAmount);
+    }
+
+    let amount_in = in_after_fee
+        .checked_mul(MAX_FEE as u128)
+        .ok_or(MathError::Overflow)?
+        .div_ceil((MAX_FEE - fee) as u128);
+    let amount_in = to_u64(amount_in)?;
+
+    Ok(SwapQuote {
+        amount_in,
+        amount_out,
+        fee: amount_in - in_after_fee as u64,
+    })
+}
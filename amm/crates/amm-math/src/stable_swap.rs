@@ -0,0 +1,123 @@
+use crate::{
+    error::MathError,
+    math::{mul_div, to_u64, MAX_FEE},
+    quote::{with_fee, SwapQuote},
+};
+
+// Curve's StableSwap invariant for two tokens:
+//   A * n^n * (x + y) + D = A * n^n * D + D^(n+1) / (n^n * x * y), with n = 2
+// Everything is done in u128 and fails with Overflow rather than wrapping for huge pools.
+
+const N_COINS: u128 = 2;
+const MAX_ITERATIONS: usize = 255;
+
+// Solves the invariant for D with Newton's method
+pub fn compute_d(amp: u64, x: u64, y: u64) -> Result<u128, MathError> {
+    let (x, y) = (x as u128, y as u128);
+    let sum = x + y;
+    if sum == 0 {
+        return Ok(0);
+    }
+    if x == 0 || y == 0 {
+        return Err(MathError::InsufficientLiquidity);
+    }
+
+    let ann = amp as u128 * N_COINS * N_COINS;
+    let mut d = sum;
+
+    for _ in 0..MAX_ITERATIONS {
+        let d_p = d_product(d, x, y)?;
+        let previous = d;
+
+        let numerator = ann
+            .checked_mul(sum)
+            .zip(d_p.checked_mul(N_COINS))
+            .and_then(|(a, b)| a.checked_add(b))
+            .ok_or(MathError::Overflow)?;
+        let denominator = ann
+            .checked_sub(1)
+            .and_then(|a| a.checked_mul(d))
+            .zip(d_p.checked_mul(N_COINS + 1))
+            .and_then(|(a, b)| a.checked_add(b))
+            .ok_or(MathError::Overflow)?;
+        d = mul_div(numerator, d, denominator, false)?;
+
+        if d.abs_diff(previous) <= 1 {
+            return Ok(d);
+        }
+    }
+
+    Err(MathError::Overflow)
+}
+
+// Balance of the other token that keeps the invariant at `d` once this one is `balance`
+pub fn compute_y(amp: u64, balance: u128, d: u128) -> Result<u128, MathError> {
+    if balance == 0 {
+        return Err(MathError::InsufficientLiquidity);
+    }
+
+    let ann = amp as u128 * N_COINS * N_COINS;
+    let c = mul_div(mul_div(d, d, balance * N_COINS, false)?, d, ann * N_COINS, false)?;
+    let b = balance + d / ann;
+    let mut y = d;
+
+    for _ in 0..MAX_ITERATIONS {
+        let previous = y;
+        let denominator = (2 * y + b).checked_sub(d).ok_or(MathError::Underflow)?;
+        y = y
+            .checked_mul(y)
+            .and_then(|a| a.checked_add(c))
+            .ok_or(MathError::Overflow)?
+            / denominator;
+
+        if y.abs_diff(previous) <= 1 {
+            return Ok(y);
+        }
+    }
+
+    Err(MathError::Overflow)
+}
+
+// D^(n+1) / (n^n * x * y), one factor at a time to stay in range
+pub(crate) fn d_product(d: u128, x: u128, y: u128) -> Result<u128, MathError> {
+    mul_div(mul_div(d, d, x * N_COINS, false)?, d, y * N_COINS, false)
+}
+
+pub fn swap(amp: u64, reserve_in: u64, reserve_out: u64, fee: u16, amount_in: u64) -> Result<SwapQuote, MathError> {
+    if fee >= MAX_FEE {
+        return Err(MathError::InvalidFeeAmount);
+    }
+
+    let d = compute_d(amp, reserve_in, reserve_out)?;
+
+    let fee_amount = (amount_in as u128 * fee as u128 / MAX_FEE as u128) as u64;
+    let in_after_fee = amount_in - fee_amount;
+
+    let new_in = reserve_in as u128 + in_after_fee as u128;
+    let new_out = compute_y(amp, new_in, d)?;
+
+    // Round against the trader so D never shrinks
+    let amount_out = (reserve_out as u128)
+        .saturating_sub(new_out)
+        .saturating_sub(1);
+
+    Ok(SwapQuote {
+        amount_in,
+        amount_out: to_u64(amount_out)?,
+        fee: fee_amount,
+    })
+}
+
+pub fn swap_exact_out(amp: u64, reserve_in: u64, reserve_out: u64, fee: u16, amount_out: u64) -> Result<SwapQuote, MathError> {
+    if amount_out >= reserve_out {
+        return Err(MathError::InsufficientLiquidity);
+    }
+
+    let d = compute_d(amp, reserve_in, reserve_out)?;
+    let new_out = (reserve_out - amount_out) as u128;
+    let new_in = compute_y(amp, new_out, d)?;
+
+    let in_after_fee = new_in.saturating_sub(reserve_in as u128) + 1;
+
+    with_fee(in_after_fee, amount_out, fee)
+}
@@ -0,0 +1,84 @@
+use amm_math::concentrated::{
+    compute_swap_step, liquidity_amounts, sqrt_price_at_tick, tick_at_sqrt_price, MAX_TICK, MIN_TICK,
+};
+use proptest::prelude::*;
+
+// Within +-110_000 ticks prices stay between 2^56 and 2^72, so with this much liquidity every
+// amount fits in a u64 and the math below must never fail
+const MAX_LIQUIDITY: u128 = 1 << 40;
+
+fn tick() -> impl Strategy<Value = i32> {
+    MIN_TICK..=MAX_TICK
+}
+
+#[test]
+fn sqrt_price_at_tick_bounds() {
+    assert_eq!(sqrt_price_at_tick(0), Ok(1 << 64));
+    assert!(sqrt_price_at_tick(MIN_TICK).unwrap() >= 1 << 32);
+    assert!(sqrt_price_at_tick(MAX_TICK).unwrap() <= 1 << 96);
+    assert!(sqrt_price_at_tick(MIN_TICK - 1).is_err());
+    assert!(sqrt_price_at_tick(MAX_TICK + 1).is_err());
+}
+
+proptest! {
+    #[test]
+    fn sqrt_price_at_tick_is_increasing(tick in MIN_TICK..MAX_TICK) {
+        prop_assert!(sqrt_price_at_tick(tick).unwrap() < sqrt_price_at_tick(tick + 1).unwrap());
+    }
+
+    #[test]
+    fn tick_at_sqrt_price_inverts(tick in tick()) {
+        let sqrt_price = sqrt_price_at_tick(tick).unwrap();
+        prop_assert_eq!(tick_at_sqrt_price(sqrt_price, MIN_TICK, MAX_TICK).unwrap(), tick);
+    }
+
+    // A price between two ticks belongs to the lower one
+    #[test]
+    fn tick_at_sqrt_price_rounds_down(tick in MIN_TICK..MAX_TICK, offset in 0u128..u128::MAX) {
+        let lower = sqrt_price_at_tick(tick).unwrap();
+        let upper = sqrt_price_at_tick(tick + 1).unwrap();
+        let sqrt_price = lower + offset % (upper - lower);
+        prop_assert_eq!(tick_at_sqrt_price(sqrt_price, MIN_TICK, MAX_TICK).unwrap(), tick);
+    }
+
+    #[test]
+    fn swap_step_stays_within_input_and_target(
+        tick in -100_000i32..100_000,
+        distance in -10_000i32..10_000,
+        liquidity in 1_000u128..MAX_LIQUIDITY,
+        amount_remaining in 1u64..u64::MAX / 2,
+        fee in 0u16..1_000,
+    ) {
+        prop_assume!(distance != 0);
+        let sqrt_price = sqrt_price_at_tick(tick).unwrap();
+        let target = sqrt_price_at_tick(tick + distance).unwrap();
+        let x_to_y = target < sqrt_price;
+
+        let step = compute_swap_step(sqrt_price, target, liquidity, amount_remaining, fee).unwrap();
+
+        prop_assert!(step.amount_in as u128 + step.fee as u128 <= amount_remaining as u128);
+        match x_to_y {
+            true => prop_assert!(step.sqrt_price >= target && step.sqrt_price <= sqrt_price),
+            false => prop_assert!(step.sqrt_price <= target && step.sqrt_price >= sqrt_price),
+        }
+        // A step that stops short of its target used all of its input
+        if step.sqrt_price != target {
+            prop_assert_eq!(step.amount_in + step.fee, amount_remaining);
+        }
+    }
+
+    // Whatever the price, adding liquidity never costs less than removing it pays out
+    #[test]
+    fn liquidity_amounts_round_in_pool_favour(
+        tick_lower in -100_000i32..100_000,
+        width in 1i32..10_000,
+        tick_current in -110_000i32..110_000,
+        liquidity in 1u128..MAX_LIQUIDITY,
+    ) {
+        let tick_upper = tick_lower + width;
+        let sqrt_price = sqrt_price_at_tick(tick_current).unwrap();
+        let deposit = liquidity_amounts(sqrt_price, tick_current, tick_lower, tick_upper, liquidity, true).unwrap();
+        let withdraw = liquidity_amounts(sqrt_price, tick_current, tick_lower, tick_upper, liquidity, false).unwrap();
+        prop_assert!(deposit.0 >= withdraw.0 && deposit.1 >= withdraw.1);
+    }
+}
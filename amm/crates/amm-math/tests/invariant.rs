@@ -0,0 +1,168 @@
+use amm_math::{
+    constant_product, full_mul, invariant, quote_deposit, quote_deposit_single, quote_swap,
+    quote_swap_exact_out, quote_withdraw, spot_price, Curve, MathError, Pool, Q64,
+};
+use proptest::prelude::*;
+
+// Reserves and amounts are kept within what real pools hold so the products fit in u128 and
+// the stable swap solver converges. Every quote inside those bounds must succeed.
+
+const MAX_RESERVE: u64 = 1_000_000_000_000_000;
+
+fn curve() -> impl Strategy<Value = Curve> {
+    prop_oneof![
+        Just(Curve::ConstantProduct),
+        (1u64..=10_000).prop_map(|amp| Curve::StableSwap { amp }),
+    ]
+}
+
+fn pool() -> impl Strategy<Value = Pool> {
+    (curve(), 0u16..1_000, 1_000u64..MAX_RESERVE, 1_000u64..MAX_RESERVE, 1_000u64..MAX_RESERVE)
+        .prop_filter("reserves too lopsided", |(_, _, x, y, _)| x / y < 1_000 && y / x < 1_000)
+        .prop_map(|(curve, fee, reserve_x, reserve_y, supply)| Pool {
+            curve,
+            fee,
+            reserve_x,
+            reserve_y,
+            supply,
+        })
+}
+
+// Compares the invariant the pool has per LP token, a / supply_a >= b / supply_b
+fn per_share_not_lower(a: &Pool, b: &Pool) -> bool {
+    match a.curve {
+        // sqrt(k) rounds, so compare k / supply^2 instead
+        Curve::ConstantProduct => {
+            let k = |p: &Pool| p.reserve_x as u128 * p.reserve_y as u128;
+            let supply = |p: &Pool| p.supply as u128 * p.supply as u128;
+            full_mul(k(a), supply(b)) >= full_mul(k(b), supply(a))
+        },
+        // D is only solved to the unit, allow for that
+        Curve::StableSwap { .. } => {
+            let (d_a, d_b) = (invariant(a).unwrap(), invariant(b).unwrap());
+            full_mul(d_a + 1, b.supply as u128) >= full_mul(d_b, a.supply as u128)
+        },
+    }
+}
+
+fn k(pool: &Pool) -> u128 {
+    pool.reserve_x as u128 * pool.reserve_y as u128
+}
+
+fn swapped(pool: &Pool, is_x: bool, amount_in: u64, amount_out: u64) -> Pool {
+    match is_x {
+        true => pool.with_reserves(pool.reserve_x + amount_in, pool.reserve_y - amount_out),
+        false => pool.with_reserves(pool.reserve_x - amount_out, pool.reserve_y + amount_in),
+    }
+}
+
+fn invariant_not_lower(after: &Pool, before: &Pool) -> bool {
+    match before.curve {
+        Curve::ConstantProduct => k(after) >= k(before),
+        Curve::StableSwap { .. } => invariant(after).unwrap() >= invariant(before).unwrap(),
+    }
+}
+
+#[test]
+fn compute_d_fails_instead_of_overflowing() {
+    assert!(amm_math::stable_swap::compute_d(u64::MAX, u64::MAX, u64::MAX).is_err());
+    assert!(amm_math::stable_swap::compute_d(0, 1_000, 1_000).is_err());
+}
+
+proptest! {
+    #[test]
+    fn swap_never_decreases_invariant(pool in pool(), is_x: bool, share in 1u64..=1_000) {
+        let (reserve_in, _) = pool.reserves(is_x);
+        let amount_in = (reserve_in as u128 * share as u128 / 1_000) as u64;
+        prop_assume!(amount_in > 0);
+
+        let quote = quote_swap(&pool, is_x, amount_in).unwrap();
+        prop_assert_eq!(quote.amount_in, amount_in);
+        prop_assert!(quote.fee <= amount_in);
+
+        let after = swapped(&pool, is_x, quote.amount_in, quote.amount_out);
+        prop_assert!(invariant_not_lower(&after, &pool));
+    }
+
+    #[test]
+    fn swap_exact_out_never_decreases_invariant(pool in pool(), is_x: bool, share in 1u64..1_000) {
+        let (_, reserve_out) = pool.reserves(is_x);
+        let amount_out = (reserve_out as u128 * share as u128 / 1_000) as u64;
+        prop_assume!(amount_out > 0);
+
+        let quote = quote_swap_exact_out(&pool, is_x, amount_out).unwrap();
+        prop_assert_eq!(quote.amount_out, amount_out);
+        prop_assert!(quote.fee <= quote.amount_in);
+
+        // The fee stays in the vault, take it out to check the curve alone holds
+        let after = swapped(&pool, is_x, quote.amount_in - quote.fee, quote.amount_out);
+        prop_assert!(invariant_not_lower(&after, &pool));
+    }
+
+    #[test]
+    fn swap_exact_out_needs_both_reserves(reserve in 0u64..MAX_RESERVE, fee in 0u16..1_000, amount_out in 1u64..MAX_RESERVE) {
+        let empty = Err(MathError::InsufficientLiquidity);
+        prop_assert_eq!(constant_product::swap_exact_out(0, reserve, fee, amount_out), empty);
+        prop_assert_eq!(constant_product::swap_exact_out(reserve, 0, fee, amount_out), empty);
+        prop_assert_eq!(constant_product::swap(0, reserve, fee, amount_out), empty);
+    }
+
+    #[test]
+    fn swap_never_beats_spot_price(pool in pool(), is_x: bool, share in 1u64..=1_000) {
+        let (reserve_in, _) = pool.reserves(is_x);
+        let amount_in = (reserve_in as u128 * share as u128 / 1_000) as u64;
+        prop_assume!(amount_in > 0);
+
+        let quote = quote_swap(&pool, is_x, amount_in).unwrap();
+        let spot = spot_price(&pool, is_x).unwrap();
+        let at_spot = full_mul((quote.amount_in - quote.fee) as u128, spot);
+        prop_assert!(full_mul(quote.amount_out as u128, Q64) <= at_spot);
+    }
+
+    #[test]
+    fn deposit_never_dilutes_lps(pool in pool(), lp_amount in 1u64..MAX_RESERVE) {
+        let fits = |reserve: u64| reserve as u128 * lp_amount as u128 / pool.supply as u128 <= (u64::MAX - reserve) as u128;
+        prop_assume!(fits(pool.reserve_x) && fits(pool.reserve_y));
+
+        let amounts = quote_deposit(&pool, lp_amount).unwrap();
+        let after = Pool {
+            reserve_x: pool.reserve_x + amounts.x,
+            reserve_y: pool.reserve_y + amounts.y,
+            supply: pool.supply + lp_amount,
+            ..pool
+        };
+        prop_assert!(per_share_not_lower(&after, &pool));
+    }
+
+    #[test]
+    fn withdraw_never_dilutes_lps(pool in pool(), share in 1u64..1_000) {
+        let lp_amount = (pool.supply as u128 * share as u128 / 1_000) as u64;
+        prop_assume!(lp_amount > 0);
+
+        let amounts = quote_withdraw(&pool, lp_amount).unwrap();
+        let after = Pool {
+            reserve_x: pool.reserve_x - amounts.x,
+            reserve_y: pool.reserve_y - amounts.y,
+            supply: pool.supply - lp_amount,
+            ..pool
+        };
+        prop_assume!(after.reserve_x > 0 && after.reserve_y > 0);
+        prop_assert!(per_share_not_lower(&after, &pool));
+    }
+
+    #[test]
+    fn deposit_single_never_dilutes_lps(pool in pool(), is_x: bool, share in 1u64..=1_000) {
+        let (reserve_in, _) = pool.reserves(is_x);
+        let amount = (reserve_in as u128 * share as u128 / 1_000) as u64;
+        prop_assume!(amount > 0);
+
+        let (lp_amount, fee) = quote_deposit_single(&pool, is_x, amount).unwrap();
+        prop_assert!(fee <= amount);
+
+        let after = Pool {
+            supply: pool.supply + lp_amount,
+            ..swapped(&pool, is_x, amount, 0)
+        };
+        prop_assert!(per_share_not_lower(&after, &pool));
+    }
+}
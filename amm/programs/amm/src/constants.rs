@@ -2,3 +2,39 @@ use anchor_lang::prelude::*;
 
 #[constant]
 pub const SEED: &str = "anchor";
+
+#[constant]
+pub const MAX_FEE: u16 = 10_000; // fees are in basis points
+
+#[constant]
+pub const MAX_PROTOCOL_FEE: u16 = 5_000; // the protocol keeps at most half of the swap fee, LPs the rest
+
+#[constant]
+pub const ORACLE_OBSERVATIONS: usize = 64; // size of the price observation ring buffer
+
+#[constant]
+pub const ORACLE_INTERVAL: i64 = 60; // minimum seconds between two stored observations
+
+#[constant]
+pub const MAX_AMP: u64 = 1_000_000; // upper bound on the stable swap amplification coefficient
+
+#[constant]
+pub const MAX_AMP_CHANGE: u64 = 10; // a ramp can at most multiply or divide A by this
+
+#[constant]
+pub const MIN_RAMP_DURATION: i64 = 24 * 60 * 60; // shortest allowed A ramp, in seconds
+
+#[constant]
+pub const MINIMUM_LIQUIDITY: u64 = 1_000; // LP tokens locked forever by the first deposit
+
+#[constant]
+pub const MIN_INITIAL_LIQUIDITY: u64 = 100 * MINIMUM_LIQUIDITY; // smallest LP supply a pool can start with
+
+#[constant]
+pub const MIN_TICK: i32 = -443_636; // sqrt price of 2^-32
+
+#[constant]
+pub const MAX_TICK: i32 = 443_636; // sqrt price of 2^32
+
+#[constant]
+pub const TICK_ARRAY_SIZE: usize = 64; // initializable ticks per tick array account
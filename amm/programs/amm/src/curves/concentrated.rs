@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::error::AmmError;
+
+// The tick and sqrt price math lives in amm-math, these only turn its errors into AmmError.
+
+pub use amm_math::concentrated::SwapStep;
+pub use amm_math::Q64;
+
+// a * b / denominator without intermediate overflow
+pub fn mul_div(a: u128, b: u128, denominator: u128, round_up: bool) -> Result<u128> {
+    Ok(amm_math::mul_div(a, b, denominator, round_up).map_err(AmmError::from)?)
+}
+
+pub fn sqrt_price_at_tick(tick: i32) -> Result<u128> {
+    Ok(amm_math::concentrated::sqrt_price_at_tick(tick).map_err(AmmError::from)?)
+}
+
+// Greatest tick in [lower, upper] whose price is at or below `sqrt_price`
+pub fn tick_at_sqrt_price(sqrt_price: u128, lower: i32, upper: i32) -> Result<i32> {
+    Ok(amm_math::concentrated::tick_at_sqrt_price(sqrt_price, lower, upper).map_err(AmmError::from)?)
+}
+
+pub fn compute_swap_step(
+    sqrt_price: u128,
+    sqrt_price_target: u128,
+    liquidity: u128,
+    amount_remaining: u64,
+    fee: u16,
+) -> Result<SwapStep> {
+    Ok(amm_math::concentrated::compute_swap_step(sqrt_price, sqrt_price_target, liquidity, amount_remaining, fee)
+        .map_err(AmmError::from)?)
+}
+
+pub fn liquidity_amounts(
+    sqrt_price: u128,
+    tick_current: i32,
+    tick_lower: i32,
+    tick_upper: i32,
+    liquidity: u128,
+    round_up: bool,
+) -> Result<(u64, u64)> {
+    Ok(amm_math::concentrated::liquidity_amounts(sqrt_price, tick_current, tick_lower, tick_upper, liquidity, round_up)
+        .map_err(AmmError::from)?)
+}
@@ -0,0 +1,99 @@
+pub mod concentrated;
+
+use amm_math::{Amounts, Curve, Pool, SwapQuote};
+use anchor_lang::prelude::*;
+use crate::{
+    error::AmmError,
+    state::{Config, CurveType},
+};
+
+// The constant product and stable swap math lives in the amm-math crate, which clients use
+// to quote. These only turn a Config and reserves into an amm_math::Pool.
+
+pub fn pool(config: &Config, reserve_x: u64, reserve_y: u64, supply: u64, now: i64) -> Result<Pool> {
+    let curve = match config.curve_type {
+        CurveType::ConstantProduct => Curve::ConstantProduct,
+        CurveType::StableSwap => Curve::StableSwap { amp: config.amp(now) },
+        CurveType::Concentrated => return err!(AmmError::UnsupportedCurveType),
+    };
+
+    Ok(Pool {
+        curve,
+        fee: config.fee,
+        reserve_x,
+        reserve_y,
+        supply,
+    })
+}
+
+// Swaps `amount_in` of token x (or y) against the reserves with the pool's curve
+pub fn swap(
+    config: &Config,
+    reserve_x: u64,
+    reserve_y: u64,
+    is_x: bool,
+    amount_in: u64,
+    now: i64,
+) -> Result<SwapQuote> {
+    let pool = pool(config, reserve_x, reserve_y, 0, now)?;
+    Ok(amm_math::quote_swap(&pool, is_x, amount_in).map_err(AmmError::from)?)
+}
+
+// Amount of token x (or y) the vault needs to receive to release exactly `amount_out`
+pub fn swap_exact_out(
+    config: &Config,
+    reserve_x: u64,
+    reserve_y: u64,
+    is_x: bool,
+    amount_out: u64,
+    now: i64,
+) -> Result<SwapQuote> {
+    let pool = pool(config, reserve_x, reserve_y, 0, now)?;
+    Ok(amm_math::quote_swap_exact_out(&pool, is_x, amount_out).map_err(AmmError::from)?)
+}
+
+// Token amounts backing `lp_amount` new LP tokens. Balanced deposits and withdrawals are
+// proportional to the reserves whatever the curve, so A and the time don't matter.
+pub fn deposit_amounts(
+    config: &Config,
+    reserve_x: u64,
+    reserve_y: u64,
+    supply: u64,
+    lp_amount: u64,
+) -> Result<Amounts> {
+    let pool = pool(config, reserve_x, reserve_y, supply, 0)?;
+    Ok(amm_math::quote_deposit(&pool, lp_amount).map_err(AmmError::from)?)
+}
+
+// Token amounts released by burning `lp_amount` LP tokens
+pub fn withdraw_amounts(
+    config: &Config,
+    reserve_x: u64,
+    reserve_y: u64,
+    supply: u64,
+    lp_amount: u64,
+) -> Result<Amounts> {
+    let pool = pool(config, reserve_x, reserve_y, supply, 0)?;
+    Ok(amm_math::quote_withdraw(&pool, lp_amount).map_err(AmmError::from)?)
+}
+
+// Value of the pool invariant that grows linearly with the LP supply
+pub fn invariant(config: &Config, reserve_x: u64, reserve_y: u64, now: i64) -> Result<u128> {
+    let pool = pool(config, reserve_x, reserve_y, 0, now)?;
+    Ok(amm_math::invariant(&pool).map_err(AmmError::from)?)
+}
+
+// LP tokens backing a deposit of `amount` of only token x (or y), and the swap fee charged on
+// the part of it that is implicitly swapped to match the pool ratio
+pub fn deposit_single_amounts(
+    config: &Config,
+    reserve_x: u64,
+    reserve_y: u64,
+    supply: u64,
+    is_x: bool,
+    amount: u64,
+    now: i64,
+) -> Result<(u64, u64)> {
+    let pool = pool(config, reserve_x, reserve_y, supply, now)?;
+    Ok(amm_math::quote_deposit_single(&pool, is_x, amount).map_err(AmmError::from)?)
+}
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use amm_math::MathError;
 
 #[error_code]
 pub enum AmmError {
@@ -8,4 +9,80 @@ pub enum AmmError {
     StillLocked,
     #[msg("Zero Amount")]
     ZeroAmount,
+    #[msg("Slippage exceeded")]
+    SlippageExceeded,
+    #[msg("Insufficient liquidity")]
+    InsufficientLiquidity,
+    #[msg("Zero output")]
+    ZeroOutput,
+    #[msg("Invalid precision")]
+    InvalidPrecision,
+    #[msg("Invalid fee amount")]
+    InvalidFeeAmount,
+    #[msg("Overflow")]
+    Overflow,
+    #[msg("Underflow")]
+    Underflow,
+    #[msg("Signer is not the pool authority")]
+    InvalidAuthority,
+    #[msg("Route hops don't connect")]
+    InvalidRoute,
+    #[msg("TWAP window must be positive")]
+    InvalidTwapWindow,
+    #[msg("Not enough oracle observations for the window")]
+    OracleNotReady,
+    #[msg("Invalid amplification coefficient")]
+    InvalidAmp,
+    #[msg("Invalid amplification ramp")]
+    InvalidRamp,
+    #[msg("Pool doesn't use the stable swap curve")]
+    NotStableSwap,
+    #[msg("First deposit is below the minimum liquidity")]
+    InitialLiquidityTooLow,
+    #[msg("Instruction doesn't support this pool's curve type")]
+    UnsupportedCurveType,
+    #[msg("Invalid tick")]
+    InvalidTick,
+    #[msg("Invalid tick spacing")]
+    InvalidTickSpacing,
+    #[msg("Invalid sqrt price")]
+    InvalidSqrtPrice,
+    #[msg("Tick array doesn't belong to the pool or doesn't hold the tick")]
+    InvalidTickArray,
+    #[msg("Swap needs more tick arrays")]
+    MissingTickArray,
+    #[msg("Position still has liquidity or fees")]
+    PositionNotEmpty,
+    #[msg("Mint x has to sort before mint y")]
+    UnsortedMints,
+    #[msg("A flash loan is waiting to be repaid")]
+    FlashLoanActive,
+    #[msg("No flash loan to repay")]
+    NoFlashLoan,
+    #[msg("Flash loan has no matching flash_repay later in the transaction")]
+    MissingFlashRepay,
+    #[msg("Flash loan was not repaid in full")]
+    FlashLoanNotRepaid,
+    #[msg("Invalid reward duration")]
+    InvalidRewardDuration,
+    #[msg("Not enough LP tokens staked")]
+    InsufficientStake,
+    #[msg("Reward vault can't cover the reward rate")]
+    InsufficientRewards,
+    #[msg("Unlock the pool before renouncing its authority")]
+    RenounceWhileLocked,
+    #[msg("Concentrated pool account is missing")]
+    MissingConcentratedPool,
+}
+
+impl From<MathError> for AmmError {
+    fn from(error: MathError) -> AmmError {
+        match error {
+            MathError::Overflow => AmmError::Overflow,
+            MathError::Underflow => AmmError::Underflow,
+            MathError::InsufficientLiquidity => AmmError::InsufficientLiquidity,
+            MathError::InvalidFeeAmount => AmmError::InvalidFeeAmount,
+            MathError::InvalidTick => AmmError::InvalidTick,
+        }
+    }
 }
@@ -0,0 +1,187 @@
+use anchor_lang::prelude::*;
+use crate::state::CurveType;
+
+#[event]
+pub struct PoolLocked {
+    pub config: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PoolUnlocked {
+    pub config: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeeUpdated {
+    pub config: Pubkey,
+    pub authority: Pubkey,
+    pub old_fee: u16,
+    pub new_fee: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityTransferred {
+    pub config: Pubkey,
+    pub old_authority: Pubkey,
+    pub new_authority: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProtocolFeeUpdated {
+    pub config: Pubkey,
+    pub authority: Pubkey,
+    pub old_protocol_fee: u16,
+    pub new_protocol_fee: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProtocolFeesCollected {
+    pub config: Pubkey,
+    pub treasury_x: Pubkey,
+    pub treasury_y: Pubkey,
+    pub amount_x: u64,
+    pub amount_y: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AmpRampStarted {
+    pub config: Pubkey,
+    pub authority: Pubkey,
+    pub amp_initial: u64,
+    pub amp_target: u64,
+    pub ramp_end: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PoolInitialized {
+    pub config: Pubkey,
+    pub initializer: Pubkey,
+    pub authority: Option<Pubkey>,
+    pub mint_x: Pubkey,
+    pub mint_y: Pubkey,
+    pub mint_lp: Option<Pubkey>, // None for concentrated pools, which have no LP token
+    pub fee: u16,
+    pub curve_type: CurveType,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LiquidityAdded {
+    pub config: Pubkey,
+    pub user: Pubkey,
+    pub amount_x: u64, // token x sent by the user
+    pub amount_y: u64, // token y sent by the user
+    pub fee_x: u64, // swap fee on the implied swap of a single-sided deposit
+    pub fee_y: u64,
+    pub lp_minted: u64,
+    pub reserve_x_before: u64,
+    pub reserve_y_before: u64,
+    pub reserve_x_after: u64,
+    pub reserve_y_after: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LiquidityRemoved {
+    pub config: Pubkey,
+    pub user: Pubkey,
+    pub amount_x: u64, // token x sent out of the vault
+    pub amount_y: u64, // token y sent out of the vault
+    pub fee_x: u64, // swap fee on the implied swap of a single-sided withdrawal
+    pub fee_y: u64,
+    pub lp_burned: u64,
+    pub reserve_x_before: u64,
+    pub reserve_y_before: u64,
+    pub reserve_x_after: u64,
+    pub reserve_y_after: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct Swapped {
+    pub config: Pubkey,
+    pub user: Pubkey,
+    pub is_x: bool, // if token x was swapped for token y
+    pub amount_in: u64, // sent by the user
+    pub amount_out: u64, // sent out of the vault
+    pub fee: u64, // paid in the input token
+    pub reserve_x_before: u64,
+    pub reserve_y_before: u64,
+    pub reserve_x_after: u64,
+    pub reserve_y_after: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FlashLoanTaken {
+    pub config: Pubkey,
+    pub borrower: Pubkey,
+    pub is_x: bool, // if token x was borrowed
+    pub amount: u64, // sent out of the vault
+    pub fee: u64, // owed on top of `amount` by the flash_repay
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FlashLoanRepaid {
+    pub config: Pubkey,
+    pub borrower: Pubkey,
+    pub is_x: bool,
+    pub amount: u64, // principal
+    pub fee: u64, // kept by the pool, minus the protocol's cut
+    pub protocol_fee: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FarmFunded {
+    pub farm: Pubkey,
+    pub authority: Pubkey,
+    pub amount: u64, // reward tokens that reached the reward vault
+    pub reward_rate: u64, // per second, leftover rewards included
+    pub end_time: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LpStaked {
+    pub farm: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub total_staked: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LpUnstaked {
+    pub farm: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub total_staked: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardsClaimed {
+    pub farm: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64, // sent out of the reward vault
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardsForfeited {
+    pub farm: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64, // booked rewards left in the vault for the next fund_farm
+    pub timestamp: i64,
+}
@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+use crate::{error::AmmError, state::Position};
+
+#[derive(Accounts)]
+pub struct ClosePosition<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        close = owner,
+        has_one = owner,
+        constraint = position.liquidity == 0 && position.fees_owed_x == 0 && position.fees_owed_y == 0 @ AmmError::PositionNotEmpty,
+    )]
+    pub position: Account<'info, Position>,
+}
@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+use crate::{
+    error::AmmError,
+    events::ProtocolFeesCollected,
+    state::Config,
+};
+
+#[derive(Accounts)]
+pub struct CollectProtocolFees<'info>{
+    pub authority: Signer<'info>,
+
+    #[account(mint::token_program = token_program_x)]
+    pub mint_x: InterfaceAccount<'info, Mint>,
+    #[account(mint::token_program = token_program_y)]
+    pub mint_y: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        has_one = mint_x,
+        has_one = mint_y,
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.config_bump,
+        constraint = config.authority == Some(authority.key()) @ AmmError::InvalidAuthority,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_x,
+        associated_token::authority = config,
+        associated_token::token_program = token_program_x,
+    )]
+    pub vault_x: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_y,
+        associated_token::authority = config,
+        associated_token::token_program = token_program_y,
+    )]
+    pub vault_y: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = mint_x,
+        token::token_program = token_program_x,
+    )]
+    pub treasury_x: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = mint_y,
+        token::token_program = token_program_y,
+    )]
+    pub treasury_y: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program_x: Interface<'info, TokenInterface>,
+    pub token_program_y: Interface<'info, TokenInterface>,
+}
+
+impl<'info> CollectProtocolFees<'info>{
+
+    pub fn collect_protocol_fees(&mut self) -> Result<()>{
+        // Fails while a flash loan is out, the lent tokens may include the protocol's share
+        self.config.reserves(self.vault_x.amount, self.vault_y.amount)?;
+
+        let amount_x = self.config.protocol_fees_x;
+        let amount_y = self.config.protocol_fees_y;
+        require!(amount_x != 0 || amount_y != 0, AmmError::ZeroAmount);
+
+        self.config.protocol_fees_x = 0;
+        self.config.protocol_fees_y = 0;
+
+        if amount_x != 0 {
+            self.withdraw_tokens(true, amount_x)?;
+        }
+        if amount_y != 0 {
+            self.withdraw_tokens(false, amount_y)?;
+        }
+
+        emit!(ProtocolFeesCollected {
+            config: self.config.key(),
+            treasury_x: self.treasury_x.key(),
+            treasury_y: self.treasury_y.key(),
+            amount_x,
+            amount_y,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn withdraw_tokens(
+        &self, 
+        is_x: bool, 
+        amount: u64
+    ) -> Result<()> {
+        let (from, to, mint, token_program) = match is_x {
+            true => (&self.vault_x, &self.treasury_x, &self.mint_x, &self.token_program_x),
+            false => (&self.vault_y, &self.treasury_y, &self.mint_y, &self.token_program_y),
+        };
+
+        let cpi_program = token_program.to_account_info();
+
+        let cpi_accounts = TransferChecked {
+            from: from.to_account_info(),
+            to: to.to_account_info(),
+            mint: mint.to_account_info(),
+            authority: self.config.to_account_info(),
+        };
+
+        let seeds = &[
+            &b"config"[..],
+            &self.config.seed.to_le_bytes(),
+            &[self.config.config_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+        transfer_checked(cpi_ctx, amount, mint.decimals)?;
+
+        Ok(())
+    }
+}
@@ -1,12 +1,17 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{mint_to, transfer, Mint, MintTo, Token, TokenAccount, Transfer},
+    token_interface::{
+        mint_to, transfer_checked, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked,
+    },
 };
-use constant_product_curve::ConstantProduct;
 use crate::{
+    constants::{MINIMUM_LIQUIDITY, MIN_INITIAL_LIQUIDITY},
+    curves,
     error::AmmError,
-    state::Config,
+    events::LiquidityAdded,
+    state::{Config, Oracle},
+    utils::{amount_after_transfer_fee, amount_before_transfer_fee},
 };
 
 #[derive(Accounts)]
@@ -14,10 +19,13 @@ pub struct Deposit<'info>{
     #[account(mut)]
     pub lp_provider: Signer<'info>,
 
-    pub mint_x: Account<'info, Mint>,
-    pub mint_y: Account<'info, Mint>,
+    #[account(mint::token_program = token_program_x)]
+    pub mint_x: InterfaceAccount<'info, Mint>,
+    #[account(mint::token_program = token_program_y)]
+    pub mint_y: InterfaceAccount<'info, Mint>,
 
     #[account(
+        mut,
         has_one = mint_x,
         has_one = mint_y,
         seeds = [b"config", config.seed.to_le_bytes().as_ref()],
@@ -25,83 +33,155 @@ pub struct Deposit<'info>{
     )]
     pub config: Account<'info, Config>,
 
+    #[account(
+        mut,
+        has_one = config,
+        seeds = [b"oracle", config.key().as_ref()],
+        bump = oracle.bump,
+    )]
+    pub oracle: Account<'info, Oracle>,
+
     #[account(
         mut,
         seeds = [b"lp", config.key().as_ref()],
         bump = config.lp_bump,
         mint::decimals = 6,
         mint::authority = config,
+        mint::token_program = token_program,
     )]
-    pub mint_lp: Account<'info, Mint>,
+    pub mint_lp: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
         associated_token::mint = mint_x,
         associated_token::authority = config,
+        associated_token::token_program = token_program_x,
     )]
-    pub vault_x: Account<'info, TokenAccount>,
+    pub vault_x: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
         associated_token::mint = mint_y,
         associated_token::authority = config,
+        associated_token::token_program = token_program_y,
     )]
-    pub vault_y: Account<'info, TokenAccount>,
+    pub vault_y: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
         associated_token::authority = lp_provider,
-        associated_token::mint = mint_x
+        associated_token::mint = mint_x,
+        associated_token::token_program = token_program_x,
     )]
-    pub lp_provider_x: Account<'info, TokenAccount>,
+    pub lp_provider_x: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
         associated_token::authority = lp_provider,
-        associated_token::mint = mint_y
+        associated_token::mint = mint_y,
+        associated_token::token_program = token_program_y,
     )]
-    pub lp_provider_y: Account<'info, TokenAccount>,
+    pub lp_provider_y: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"dead", config.key().as_ref()],
+        bump,
+        token::mint = mint_lp,
+        token::authority = dead_lp,
+        token::token_program = token_program,
+    )]
+    pub dead_lp: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         init_if_needed,
         payer = lp_provider,
         associated_token::authority = lp_provider,
-        associated_token::mint = mint_lp
+        associated_token::mint = mint_lp,
+        associated_token::token_program = token_program,
     )]
-    pub lp_provider_lp: Account<'info, TokenAccount>,
+    pub lp_provider_lp: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub token_program_x: Interface<'info, TokenInterface>,
+    pub token_program_y: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
 impl<'info> Deposit<'info>{
 
+    // On the first deposit `max_x` and `max_y` are deposited as is, the LP supply starts at
+    // the pool invariant (sqrt(x * y) for the constant product) and `lp_amount` is the least
+    // the user accepts once MINIMUM_LIQUIDITY is locked away
     pub fn deposit(
         &mut self,
         lp_amount: u64, // amount of mint_lp the user wants to claim
         max_x: u64, // maximum amount of token x the user is willing to deposit
         max_y: u64 // maximum amount of token y the user is willing to deposit
     ) -> Result<()>{
-        let condition = self.mint_lp.supply == 0 && self.vault_x.amount == 0 && self.vault_y.amount == 0;
-        let(x,y) = match condition{
-            true => (max_x, max_y),
+        let (reserve_x, reserve_y) = self.config.reserves(self.vault_x.amount, self.vault_y.amount)?;
+        let now = Clock::get()?.unix_timestamp;
+        self.oracle.update(&mut self.config, reserve_x, reserve_y, now);
+        let condition = self.mint_lp.supply == 0;
+        let(x, y, lp_amount) = match condition{
+            true => {
+                let liquidity = curves::invariant(
+                    &self.config,
+                    amount_after_transfer_fee(&self.mint_x, max_x)?,
+                    amount_after_transfer_fee(&self.mint_y, max_y)?,
+                    now,
+                )?;
+                let liquidity = u64::try_from(liquidity).map_err(|_| AmmError::Overflow)?;
+                require!(liquidity >= MIN_INITIAL_LIQUIDITY, AmmError::InitialLiquidityTooLow);
+
+                let user_lp = liquidity - MINIMUM_LIQUIDITY;
+                require!(user_lp >= lp_amount, AmmError::SlippageExceeded);
+
+                self.mint_lp_tokens(self.dead_lp.to_account_info(), MINIMUM_LIQUIDITY)?;
+                (max_x, max_y, user_lp)
+            },
             false => {
-                let amounts = ConstantProduct::xy_deposit_amounts_from_l(
-                    self.vault_x.amount, 
-                    self.vault_y.amount, 
+                let amounts = curves::deposit_amounts(
+                    &self.config,
+                    reserve_x, 
+                    reserve_y, 
                     self.mint_lp.supply, 
-                    lp_amount, 
-                    6
-                ).unwrap();
-                (amounts.x, amounts.y)
+                    lp_amount,
+                )?;
+                // Gross up so the vaults receive what the curve asks for after transfer fees
+                (
+                    amount_before_transfer_fee(&self.mint_x, amounts.x)?,
+                    amount_before_transfer_fee(&self.mint_y, amounts.y)?,
+                    lp_amount,
+                )
             },
         };
 
-        require!(x <= max_x && y <= max_y, AmmError::IncorrectAmmount);
+        require!(x <= max_x && y <= max_y, AmmError::SlippageExceeded);
         self.deposit_tokens(true, x)?;
         self.deposit_tokens(false, y)?;
-        self.mint_lp_tokens(lp_amount)?;
+        self.mint_lp_tokens(self.lp_provider_lp.to_account_info(), lp_amount)?;
+
+        self.vault_x.reload()?;
+        self.vault_y.reload()?;
+        let (reserve_x_after, reserve_y_after) = self.config.reserves(self.vault_x.amount, self.vault_y.amount)?;
+
+        emit!(LiquidityAdded {
+            config: self.config.key(),
+            user: self.lp_provider.key(),
+            amount_x: x,
+            amount_y: y,
+            fee_x: 0,
+            fee_y: 0,
+            lp_minted: lp_amount,
+            reserve_x_before: reserve_x,
+            reserve_y_before: reserve_y,
+            reserve_x_after,
+            reserve_y_after,
+            timestamp: now,
+        });
+
         Ok(())
     }
 
@@ -110,29 +190,30 @@ impl<'info> Deposit<'info>{
         is_x: bool,
         amount: u64
     ) -> Result<()>{
-        let cpi_program = self.token_program.to_account_info();
-
-        let (from, to) = match is_x {
-            true =>( self.lp_provider_x.to_account_info(),  self.vault_x.to_account_info()),
-            false => (self.lp_provider_y.to_account_info(), self.vault_y.to_account_info()),           
+        let (from, to, mint, token_program) = match is_x {
+            true =>( &self.lp_provider_x, &self.vault_x, &self.mint_x, &self.token_program_x),
+            false => (&self.lp_provider_y, &self.vault_y, &self.mint_y, &self.token_program_y),           
         };
-        let cpi_accounts = Transfer{
+
+        let cpi_program = token_program.to_account_info();
+        let cpi_accounts = TransferChecked{
             from: from.to_account_info(),
             to: to.to_account_info(),
+            mint: mint.to_account_info(),
             authority: self.lp_provider.to_account_info(),
         };
 
         let ctx = CpiContext::new(cpi_program, cpi_accounts);
-        transfer(ctx, amount)?;
+        transfer_checked(ctx, amount, mint.decimals)?;
         Ok(())
     }
 
-    pub fn mint_lp_tokens(&mut self, amount: u64) -> Result<()> {
+    pub fn mint_lp_tokens(&self, to: AccountInfo<'info>, amount: u64) -> Result<()> {
         let cpi_program = self.token_program.to_account_info(); 
 
         let cpi_accounts = MintTo{
             mint: self.mint_lp.to_account_info(),
-            to: self.lp_provider_lp.to_account_info(),
+            to,
             authority: self.config.to_account_info(),
         };
 
@@ -140,7 +221,7 @@ impl<'info> Deposit<'info>{
 
         let seeds = &[
             &b"config"[..],
-            &config_seeds.as_ref(),
+            config_seeds.as_ref(),
             &[self.config.config_bump],
         ];
 
@@ -0,0 +1,227 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        mint_to, transfer_checked, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked,
+    },
+};
+use crate::{
+    curves,
+    error::AmmError,
+    events::LiquidityAdded,
+    state::{Config, Oracle, PoolStats},
+    utils::amount_after_transfer_fee,
+};
+
+#[derive(Accounts)]
+pub struct DepositSingle<'info>{
+    #[account(mut)]
+    pub lp_provider: Signer<'info>,
+
+    #[account(mint::token_program = token_program_x)]
+    pub mint_x: InterfaceAccount<'info, Mint>,
+    #[account(mint::token_program = token_program_y)]
+    pub mint_y: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        has_one = mint_x,
+        has_one = mint_y,
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.config_bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        has_one = config,
+        seeds = [b"oracle", config.key().as_ref()],
+        bump = oracle.bump,
+    )]
+    pub oracle: Account<'info, Oracle>,
+
+    #[account(
+        mut,
+        has_one = config,
+        seeds = [b"stats", config.key().as_ref()],
+        bump = pool_stats.bump,
+    )]
+    pub pool_stats: Account<'info, PoolStats>,
+
+    #[account(
+        mut,
+        seeds = [b"lp", config.key().as_ref()],
+        bump = config.lp_bump,
+        mint::decimals = 6,
+        mint::authority = config,
+        mint::token_program = token_program,
+    )]
+    pub mint_lp: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_x,
+        associated_token::authority = config,
+        associated_token::token_program = token_program_x,
+    )]
+    pub vault_x: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_y,
+        associated_token::authority = config,
+        associated_token::token_program = token_program_y,
+    )]
+    pub vault_y: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = lp_provider,
+        associated_token::authority = lp_provider,
+        associated_token::mint = mint_x,
+        associated_token::token_program = token_program_x,
+    )]
+    pub lp_provider_x: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = lp_provider,
+        associated_token::authority = lp_provider,
+        associated_token::mint = mint_y,
+        associated_token::token_program = token_program_y,
+    )]
+    pub lp_provider_y: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = lp_provider,
+        associated_token::authority = lp_provider,
+        associated_token::mint = mint_lp,
+        associated_token::token_program = token_program,
+    )]
+    pub lp_provider_lp: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub token_program_x: Interface<'info, TokenInterface>,
+    pub token_program_y: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> DepositSingle<'info>{
+
+    // Deposits only token x (or y), the pool implicitly swaps the share that doesn't match
+    // its ratio and charges the swap fee on it
+    pub fn deposit_single(
+        &mut self,
+        is_x: bool,
+        amount_in: u64, // amount of token x or y the user sends
+        min_lp_out: u64 // minimum amount of mint_lp the user is willing to receive
+    ) -> Result<()>{
+        require!(!self.config.locked, AmmError::StillLocked);
+        require!(amount_in != 0, AmmError::ZeroAmount);
+
+        let (reserve_x, reserve_y) = self.config.reserves(self.vault_x.amount, self.vault_y.amount)?;
+        let now = Clock::get()?.unix_timestamp;
+        self.oracle.update(&mut self.config, reserve_x, reserve_y, now);
+
+        // The pool only gets credited what reaches the vault after the transfer fee
+        let mint_in = match is_x {
+            true => &self.mint_x,
+            false => &self.mint_y,
+        };
+        let received = amount_after_transfer_fee(mint_in, amount_in)?;
+
+        let (lp_amount, fee) = curves::deposit_single_amounts(
+            &self.config,
+            reserve_x,
+            reserve_y,
+            self.mint_lp.supply,
+            is_x,
+            received,
+            now,
+        )?;
+        require!(lp_amount != 0, AmmError::ZeroOutput);
+        require!(lp_amount >= min_lp_out, AmmError::SlippageExceeded);
+
+        self.config.accrue_protocol_fee(is_x, fee)?;
+        self.pool_stats.record_fee(is_x, fee);
+
+        self.deposit_tokens(is_x, amount_in)?;
+        self.mint_lp_tokens(lp_amount)?;
+
+        self.vault_x.reload()?;
+        self.vault_y.reload()?;
+        let (reserve_x_after, reserve_y_after) = self.config.reserves(self.vault_x.amount, self.vault_y.amount)?;
+
+        let (amount_x, amount_y, fee_x, fee_y) = match is_x {
+            true => (amount_in, 0, fee, 0),
+            false => (0, amount_in, 0, fee),
+        };
+
+        emit!(LiquidityAdded {
+            config: self.config.key(),
+            user: self.lp_provider.key(),
+            amount_x,
+            amount_y,
+            fee_x,
+            fee_y,
+            lp_minted: lp_amount,
+            reserve_x_before: reserve_x,
+            reserve_y_before: reserve_y,
+            reserve_x_after,
+            reserve_y_after,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    pub fn deposit_tokens(
+        &mut self,
+        is_x: bool,
+        amount: u64
+    ) -> Result<()>{
+        let (from, to, mint, token_program) = match is_x {
+            true =>( &self.lp_provider_x, &self.vault_x, &self.mint_x, &self.token_program_x),
+            false => (&self.lp_provider_y, &self.vault_y, &self.mint_y, &self.token_program_y),           
+        };
+
+        let cpi_program = token_program.to_account_info();
+        let cpi_accounts = TransferChecked{
+            from: from.to_account_info(),
+            to: to.to_account_info(),
+            mint: mint.to_account_info(),
+            authority: self.lp_provider.to_account_info(),
+        };
+
+        let ctx = CpiContext::new(cpi_program, cpi_accounts);
+        transfer_checked(ctx, amount, mint.decimals)?;
+        Ok(())
+    }
+
+    pub fn mint_lp_tokens(&mut self, amount: u64) -> Result<()> {
+        let cpi_program = self.token_program.to_account_info(); 
+
+        let cpi_accounts = MintTo{
+            mint: self.mint_lp.to_account_info(),
+            to: self.lp_provider_lp.to_account_info(),
+            authority: self.config.to_account_info(),
+        };
+
+        let config_seeds = self.config.seed.to_le_bytes();
+
+        let seeds = &[
+            &b"config"[..],
+            config_seeds.as_ref(),
+            &[self.config.config_bump],
+        ];
+
+        let signer_seeds = &[&seeds[..]];
+
+        let ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+        mint_to(ctx, amount)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,249 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+    Discriminator,
+};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked}
+};
+use crate::{
+    error::AmmError,
+    events::{FlashLoanRepaid, FlashLoanTaken},
+    instruction::FlashRepay,
+    state::{Config, CurveType, FlashLoanState, PoolStats},
+    utils::amount_before_transfer_fee,
+};
+
+// flash_loan and flash_repay take the same accounts, so a loan is matched with its
+// repayment by comparing the account lists of the two instructions
+#[derive(Accounts)]
+pub struct FlashLoan<'info>{
+    #[account(mut)]
+    pub borrower: Signer<'info>,
+
+    #[account(mint::token_program = token_program_x)]
+    pub mint_x: InterfaceAccount<'info, Mint>,
+    #[account(mint::token_program = token_program_y)]
+    pub mint_y: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = borrower,
+        associated_token::mint = mint_x,
+        associated_token::authority = borrower,
+        associated_token::token_program = token_program_x,
+    )]
+    pub borrower_x: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = borrower,
+        associated_token::mint = mint_y,
+        associated_token::authority = borrower,
+        associated_token::token_program = token_program_y,
+    )]
+    pub borrower_y: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        has_one = mint_x,
+        has_one = mint_y,
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.config_bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        has_one = config,
+        seeds = [b"stats", config.key().as_ref()],
+        bump = pool_stats.bump,
+    )]
+    pub pool_stats: Account<'info, PoolStats>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_x,
+        associated_token::authority = config,
+        associated_token::token_program = token_program_x,
+    )]
+    pub vault_x: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_y,
+        associated_token::authority = config,
+        associated_token::token_program = token_program_y,
+    )]
+    pub vault_y: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: This is safe
+    pub instruction_sysvar: AccountInfo<'info>,
+
+    pub token_program_x: Interface<'info, TokenInterface>,
+    pub token_program_y: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> FlashLoan <'info>{
+
+    // Lends `amount` of token x (or y) out of the vault. The pool's swap fee is owed on top
+    // and the transaction has to repay both with a flash_repay further down.
+    pub fn flash_loan(&mut self, is_x: bool, amount: u64) -> Result<()> {
+        require!(!self.config.locked, AmmError::StillLocked);
+        require!(self.config.curve_type != CurveType::Concentrated, AmmError::UnsupportedCurveType);
+        require!(amount > 0, AmmError::ZeroAmount);
+
+        // Also fails if another loan is still open
+        let (reserve_x, reserve_y) = self.config.reserves(self.vault_x.amount, self.vault_y.amount)?;
+        let (reserve, vault_balance) = match is_x {
+            true => (reserve_x, self.vault_x.amount),
+            false => (reserve_y, self.vault_y.amount),
+        };
+        require!(amount <= reserve, AmmError::InsufficientLiquidity);
+
+        self.verify_flash_repay()?;
+
+        let fee = (amount as u128 * self.config.fee as u128).div_ceil(10_000) as u64;
+        self.config.flash_loan = Some(FlashLoanState {
+            is_x,
+            amount,
+            fee,
+            vault_balance,
+        });
+
+        self.lend_tokens(is_x, amount)?;
+
+        emit!(FlashLoanTaken {
+            config: self.config.key(),
+            borrower: self.borrower.key(),
+            is_x,
+            amount,
+            fee,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    // Tops the vault back up to its balance before the loan plus the fee. The fee stays in
+    // the vault for the LPs, except for the protocol's cut.
+    pub fn flash_repay(&mut self) -> Result<()> {
+        let loan = self.config.flash_loan.ok_or(AmmError::NoFlashLoan)?;
+        let owed = loan.vault_balance.checked_add(loan.fee).ok_or(AmmError::Overflow)?;
+
+        let (vault, mint) = match loan.is_x {
+            true => (&self.vault_x, &self.mint_x),
+            false => (&self.vault_y, &self.mint_y),
+        };
+        let missing = owed.saturating_sub(vault.amount);
+        if missing > 0 {
+            let amount = amount_before_transfer_fee(mint, missing)?;
+            self.repay_tokens(loan.is_x, amount)?;
+        }
+
+        let vault_balance = match loan.is_x {
+            true => {
+                self.vault_x.reload()?;
+                self.vault_x.amount
+            },
+            false => {
+                self.vault_y.reload()?;
+                self.vault_y.amount
+            },
+        };
+        require!(vault_balance >= owed, AmmError::FlashLoanNotRepaid);
+
+        self.config.flash_loan = None;
+        let protocol_fee = self.config.accrue_protocol_fee(loan.is_x, loan.fee)?;
+        self.pool_stats.record_fee(loan.is_x, loan.fee);
+
+        emit!(FlashLoanRepaid {
+            config: self.config.key(),
+            borrower: self.borrower.key(),
+            is_x: loan.is_x,
+            amount: loan.amount,
+            fee: loan.fee,
+            protocol_fee,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    // Looks through the instructions after this one for a flash_repay with the same accounts.
+    // The loan stays open until a flash_repay on this pool runs, so if that instruction
+    // fails or another repayment closed the loan first the whole transaction fails.
+    fn verify_flash_repay(&self) -> Result<()> {
+        let sysvar = self.instruction_sysvar.to_account_info();
+        let current = load_current_index_checked(&sysvar)? as usize;
+        let loan_ix = load_instruction_at_checked(current, &sysvar)?;
+
+        let mut index = current + 1;
+        while let Ok(ix) = load_instruction_at_checked(index, &sysvar) {
+            if ix.program_id == crate::ID
+                && ix.data.starts_with(&FlashRepay::DISCRIMINATOR)
+                && ix.accounts == loan_ix.accounts
+            {
+                return Ok(());
+            }
+            index += 1;
+        }
+
+        err!(AmmError::MissingFlashRepay)
+    }
+
+    pub fn lend_tokens(&self, is_x: bool, amount: u64) -> Result<()> {
+        let (from, to, mint, token_program) = match is_x {
+            true => (&self.vault_x, &self.borrower_x, &self.mint_x, &self.token_program_x),
+            false => (&self.vault_y, &self.borrower_y, &self.mint_y, &self.token_program_y),
+        };
+
+        let cpi_program = token_program.to_account_info();
+
+        let accounts = TransferChecked {
+            from: from.to_account_info(),
+            to: to.to_account_info(),
+            mint: mint.to_account_info(),
+            authority: self.config.to_account_info(),
+        };
+
+        let seeds = &[
+            &b"config"[..],
+            &self.config.seed.to_le_bytes(),
+            &[self.config.config_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, accounts, signer_seeds);
+
+        transfer_checked(cpi_ctx, amount, mint.decimals)?;
+
+        Ok(())
+    }
+
+    pub fn repay_tokens(&self, is_x: bool, amount: u64) -> Result<()> {
+        let (from, to, mint, token_program) = match is_x {
+            true => (&self.borrower_x, &self.vault_x, &self.mint_x, &self.token_program_x),
+            false => (&self.borrower_y, &self.vault_y, &self.mint_y, &self.token_program_y),
+        };
+
+        let cpi_program = token_program.to_account_info();
+
+        let accounts = TransferChecked {
+            from: from.to_account_info(),
+            to: to.to_account_info(),
+            mint: mint.to_account_info(),
+            authority: self.borrower.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(cpi_program, accounts);
+
+        transfer_checked(cpi_ctx, amount, mint.decimals)?;
+
+        Ok(())
+    }
+}
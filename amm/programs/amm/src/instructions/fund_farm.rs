@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+use crate::{
+    error::AmmError,
+    events::FarmFunded,
+    state::{Config, Farm},
+};
+
+#[derive(Accounts)]
+pub struct FundFarm<'info>{
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.config_bump,
+        constraint = config.authority == Some(authority.key()) @ AmmError::InvalidAuthority,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        has_one = config,
+        has_one = reward_mint,
+        seeds = [b"farm", config.key().as_ref(), reward_mint.key().as_ref()],
+        bump = farm.bump,
+    )]
+    pub farm: Account<'info, Farm>,
+
+    #[account(
+        mut,
+        associated_token::mint = reward_mint,
+        associated_token::authority = farm,
+        associated_token::token_program = reward_token_program,
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = reward_mint,
+        token::token_program = reward_token_program,
+    )]
+    pub funder_reward: InterfaceAccount<'info, TokenAccount>,
+
+    pub reward_token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> FundFarm<'info>{
+
+    // Pays `amount` of rewards, plus whatever the current schedule has not paid out yet and
+    // the rewards nobody was staked to earn, evenly over the next `duration` seconds
+    pub fn fund_farm(&mut self, amount: u64, duration: u64) -> Result<()> {
+        require!(amount > 0, AmmError::ZeroAmount);
+        require!(duration > 0, AmmError::InvalidRewardDuration);
+
+        let now = Clock::get()?.unix_timestamp;
+        let end_time = i64::try_from(duration)
+            .ok()
+            .and_then(|duration| now.checked_add(duration))
+            .ok_or(AmmError::InvalidRewardDuration)?;
+
+        self.farm.update(now);
+        let leftover = self.farm.remaining_rewards(now);
+        self.farm.undistributed = 0;
+
+        // Count what reached the vault, the reward mint can charge transfer fees
+        let balance_before = self.reward_vault.amount;
+        self.deposit_rewards(amount)?;
+        self.reward_vault.reload()?;
+        let received = self.reward_vault.amount - balance_before;
+
+        let reward_rate = received.checked_add(leftover).ok_or(AmmError::Overflow)? / duration;
+        require!(reward_rate > 0, AmmError::ZeroAmount);
+        require!(
+            reward_rate.checked_mul(duration).ok_or(AmmError::Overflow)? <= self.reward_vault.amount,
+            AmmError::InsufficientRewards
+        );
+
+        self.farm.reward_rate = reward_rate;
+        self.farm.last_update = now;
+        self.farm.end_time = end_time;
+
+        emit!(FarmFunded {
+            farm: self.farm.key(),
+            authority: self.authority.key(),
+            amount: received,
+            reward_rate,
+            end_time,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    pub fn deposit_rewards(&self, amount: u64) -> Result<()> {
+        let cpi_program = self.reward_token_program.to_account_info();
+
+        let accounts = TransferChecked {
+            from: self.funder_reward.to_account_info(),
+            to: self.reward_vault.to_account_info(),
+            mint: self.reward_mint.to_account_info(),
+            authority: self.authority.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(cpi_program, accounts);
+
+        transfer_checked(cpi_ctx, amount, self.reward_mint.decimals)?;
+
+        Ok(())
+    }
+}
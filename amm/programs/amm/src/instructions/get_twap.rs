@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::get_associated_token_address_with_program_id,
+    token_interface::TokenAccount,
+};
+use crate::{
+    error::AmmError,
+    state::{ConcentratedPool, Config, CurveType, Oracle, Twap},
+};
+
+#[derive(Accounts)]
+pub struct GetTwap<'info>{
+    #[account(
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.config_bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        has_one = config,
+        seeds = [b"oracle", config.key().as_ref()],
+        bump = oracle.bump,
+    )]
+    pub oracle: Account<'info, Oracle>,
+
+    // The vaults give the live price of the other pools
+    #[account(
+        address = get_associated_token_address_with_program_id(
+            &config.key(),
+            &config.mint_x,
+            vault_x.to_account_info().owner,
+        ),
+    )]
+    pub vault_x: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        address = get_associated_token_address_with_program_id(
+            &config.key(),
+            &config.mint_y,
+            vault_y.to_account_info().owner,
+        ),
+    )]
+    pub vault_y: InterfaceAccount<'info, TokenAccount>,
+
+    // Only for concentrated pools, its sqrt_price is the live price
+    #[account(
+        seeds = [b"concentrated", config.key().as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Option<Account<'info, ConcentratedPool>>,
+}
+
+impl<'info> GetTwap <'info>{
+
+    // Read-only, meant to be simulated or called through CPI and read from the return data
+    pub fn get_twap(&self, window: i64) -> Result<Twap> {
+        let prices = match self.config.curve_type == CurveType::Concentrated {
+            true => {
+                let pool = self.pool.as_ref().ok_or(AmmError::MissingConcentratedPool)?;
+                Some(Config::prices_concentrated(pool.sqrt_price))
+            }
+            false => {
+                let (reserve_x, reserve_y) = self.config.reserves(self.vault_x.amount, self.vault_y.amount)?;
+                Config::prices(reserve_x, reserve_y)
+            }
+        };
+
+        let now = Clock::get()?.unix_timestamp;
+        let price_cumulative = self.config.price_cumulative_at(prices, now);
+
+        self.oracle.twap(window, now, price_cumulative)
+    }
+}
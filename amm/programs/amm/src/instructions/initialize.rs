@@ -1,17 +1,27 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{ Mint, Token, TokenAccount},
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+use crate::{
+    constants::{MAX_AMP, MAX_FEE},
+    error::AmmError,
+    events::PoolInitialized,
+    state::{Config, CurveType, Oracle, PoolIndex, PoolPair, PoolRegistry, PoolStats},
 };
-use crate::state::Config;
 
 #[derive(Accounts)]
-#[instruction(seed: u64)]
+#[instruction(seed: u64, fee: u16)]
 pub struct Initialize<'info> {
     #[account(mut)]
     pub initializer: Signer<'info>,
-    pub mint_x: Account<'info, Mint>,
-    pub mint_y: Account<'info, Mint>,
+    #[account(mint::token_program = token_program_x)]
+    pub mint_x: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mint::token_program = token_program_y,
+        constraint = mint_x.key() < mint_y.key() @ AmmError::UnsortedMints,
+    )]
+    pub mint_y: Box<InterfaceAccount<'info, Mint>>,
     
     #[account(
         init,
@@ -20,36 +30,99 @@ pub struct Initialize<'info> {
         bump,
         mint::decimals =6,
         mint::authority = config,
+        mint::token_program = token_program,
     )]
-    pub mint_lp: Account<'info, Mint>, // liquidity provider token: when ever we provide liquidity for amm, we get LP tokens
+    pub mint_lp: Box<InterfaceAccount<'info, Mint>>, // liquidity provider token: when ever we provide liquidity for amm, we get LP tokens
 
     #[account(
         init,
         payer = initializer,
         associated_token::mint = mint_x,
         associated_token::authority = config,
+        associated_token::token_program = token_program_x,
     )]
-    pub vault_x: Account<'info, TokenAccount>,
+    pub vault_x: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         init,
         payer = initializer,
         associated_token::mint = mint_y,
         associated_token::authority = config,
+        associated_token::token_program = token_program_y,
     )]
-    pub vault_y: Account<'info, TokenAccount>,
+    pub vault_y: Box<InterfaceAccount<'info, TokenAccount>>,
 
+    // Holds the MINIMUM_LIQUIDITY locked by the first deposit. The program never signs for it.
     #[account(
         init,
         payer = initializer,
-        space = Config::INIT_SPACE,
+        seeds = [b"dead", config.key().as_ref()],
+        bump,
+        token::mint = mint_lp,
+        token::authority = dead_lp,
+        token::token_program = token_program,
+    )]
+    pub dead_lp: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = initializer,
+        space = 8 + Config::INIT_SPACE,
         seeds = [b"config", seed.to_le_bytes().as_ref()],
         bump,
         
     )]
     pub config: Account<'info, Config>,
 
-    pub token_program: Program<'info,Token>,
+    #[account(
+        init,
+        payer = initializer,
+        space = 8 + Oracle::INIT_SPACE,
+        seeds = [b"oracle", config.key().as_ref()],
+        bump,
+    )]
+    pub oracle: Account<'info, Oracle>,
+
+    #[account(
+        init,
+        payer = initializer,
+        space = 8 + PoolStats::INIT_SPACE,
+        seeds = [b"stats", config.key().as_ref()],
+        bump,
+    )]
+    pub pool_stats: Account<'info, PoolStats>,
+
+    #[account(
+        init_if_needed,
+        payer = initializer,
+        space = 8 + PoolRegistry::INIT_SPACE,
+        seeds = [b"registry"],
+        bump,
+    )]
+    pub registry: Account<'info, PoolRegistry>,
+
+    // Only one pool per pair and fee tier, a second one fails to create this account
+    #[account(
+        init,
+        payer = initializer,
+        space = 8 + PoolPair::INIT_SPACE,
+        seeds = [b"pair", mint_x.key().as_ref(), mint_y.key().as_ref(), fee.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub pair: Account<'info, PoolPair>,
+
+    #[account(
+        init,
+        payer = initializer,
+        space = 8 + PoolIndex::INIT_SPACE,
+        seeds = [b"pool_index", registry.pool_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub pool_index: Account<'info, PoolIndex>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub token_program_x: Interface<'info, TokenInterface>,
+    pub token_program_y: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
@@ -60,8 +133,24 @@ impl<'info> Initialize <'info>{
         seed: u64,
         fee: u16,
         authority: Option<Pubkey>, // you can pass Some(Pubkey) or None
+        curve_type: CurveType,
+        amp: u64, // amplification coefficient, only used by stable swap pools
         bumps: &InitializeBumps,
     ) -> Result<()>{
+        require!(fee < MAX_FEE, AmmError::InvalidFeeAmount);
+        // Concentrated pools have no LP mint and start at a price, see initialize_concentrated
+        require!(curve_type != CurveType::Concentrated, AmmError::UnsupportedCurveType);
+
+        let amp = match curve_type {
+            CurveType::ConstantProduct | CurveType::Concentrated => 0,
+            CurveType::StableSwap => {
+                require!(amp > 0 && amp <= MAX_AMP, AmmError::InvalidAmp);
+                amp
+            },
+        };
+
+        let now = Clock::get()?.unix_timestamp;
+
         self.config.set_inner(Config { 
             seed, 
             authority, 
@@ -70,7 +159,64 @@ impl<'info> Initialize <'info>{
             fee, 
             locked: false, 
             config_bump: bumps.config, 
-            lp_bump: bumps.mint_lp});
+            lp_bump: bumps.mint_lp,
+            protocol_fee: 0,
+            protocol_fees_x: 0,
+            protocol_fees_y: 0,
+            price_x_cumulative: 0,
+            price_y_cumulative: 0,
+            last_update: now,
+            curve_type,
+            amp_initial: amp,
+            amp_target: amp,
+            ramp_start: now,
+            ramp_end: now,
+            flash_loan: None,
+        });
+
+        self.oracle.set_inner(Oracle::new(self.config.key(), bumps.oracle, now));
+
+        self.pool_stats.set_inner(PoolStats {
+            config: self.config.key(),
+            volume_x: 0,
+            volume_y: 0,
+            fees_x: 0,
+            fees_y: 0,
+            swap_count: 0,
+            bump: bumps.pool_stats,
+        });
+
+        self.pair.set_inner(PoolPair {
+            config: self.config.key(),
+            mint_x: self.mint_x.key(),
+            mint_y: self.mint_y.key(),
+            fee,
+            index: self.registry.pool_count,
+            payer: self.initializer.key(),
+            bump: bumps.pair,
+        });
+
+        self.pool_index.set_inner(PoolIndex {
+            config: self.config.key(),
+            bump: bumps.pool_index,
+        });
+
+        self.registry.bump = bumps.registry;
+        self.registry.pool_count = self.registry.pool_count
+            .checked_add(1)
+            .ok_or(AmmError::Overflow)?;
+
+        emit!(PoolInitialized {
+            config: self.config.key(),
+            initializer: self.initializer.key(),
+            authority,
+            mint_x: self.mint_x.key(),
+            mint_y: self.mint_y.key(),
+            mint_lp: Some(self.mint_lp.key()),
+            fee,
+            curve_type,
+            timestamp: now,
+        });
 
         Ok(())
     }
@@ -0,0 +1,217 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+use crate::{
+    constants::{MAX_FEE, MAX_TICK, MIN_TICK},
+    curves::concentrated::{sqrt_price_at_tick, tick_at_sqrt_price},
+    error::AmmError,
+    events::PoolInitialized,
+    state::{ConcentratedPool, Config, CurveType, Oracle, PoolIndex, PoolPair, PoolRegistry, PoolStats},
+};
+
+// Creates a concentrated liquidity pool together with its starting price, so nobody can get
+// in between and set the price. Liquidity lives in positions, there is no LP mint.
+#[derive(Accounts)]
+#[instruction(seed: u64, fee: u16)]
+pub struct InitializeConcentrated<'info> {
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    #[account(mint::token_program = token_program_x)]
+    pub mint_x: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mint::token_program = token_program_y,
+        constraint = mint_x.key() < mint_y.key() @ AmmError::UnsortedMints,
+    )]
+    pub mint_y: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = initializer,
+        associated_token::mint = mint_x,
+        associated_token::authority = config,
+        associated_token::token_program = token_program_x,
+    )]
+    pub vault_x: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = initializer,
+        associated_token::mint = mint_y,
+        associated_token::authority = config,
+        associated_token::token_program = token_program_y,
+    )]
+    pub vault_y: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = initializer,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [b"config", seed.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        init,
+        payer = initializer,
+        space = 8 + ConcentratedPool::INIT_SPACE,
+        seeds = [b"concentrated", config.key().as_ref()],
+        bump,
+    )]
+    pub pool: Box<Account<'info, ConcentratedPool>>,
+
+    #[account(
+        init,
+        payer = initializer,
+        space = 8 + Oracle::INIT_SPACE,
+        seeds = [b"oracle", config.key().as_ref()],
+        bump,
+    )]
+    pub oracle: Box<Account<'info, Oracle>>,
+
+    #[account(
+        init,
+        payer = initializer,
+        space = 8 + PoolStats::INIT_SPACE,
+        seeds = [b"stats", config.key().as_ref()],
+        bump,
+    )]
+    pub pool_stats: Box<Account<'info, PoolStats>>,
+
+    #[account(
+        init_if_needed,
+        payer = initializer,
+        space = 8 + PoolRegistry::INIT_SPACE,
+        seeds = [b"registry"],
+        bump,
+    )]
+    pub registry: Box<Account<'info, PoolRegistry>>,
+
+    // Only one pool per pair and fee tier, a second one fails to create this account
+    #[account(
+        init,
+        payer = initializer,
+        space = 8 + PoolPair::INIT_SPACE,
+        seeds = [b"pair", mint_x.key().as_ref(), mint_y.key().as_ref(), fee.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub pair: Box<Account<'info, PoolPair>>,
+
+    #[account(
+        init,
+        payer = initializer,
+        space = 8 + PoolIndex::INIT_SPACE,
+        seeds = [b"pool_index", registry.pool_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub pool_index: Box<Account<'info, PoolIndex>>,
+
+    pub token_program_x: Interface<'info, TokenInterface>,
+    pub token_program_y: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> InitializeConcentrated<'info> {
+
+    pub fn init(
+        &mut self,
+        seed: u64,
+        fee: u16,
+        authority: Option<Pubkey>,
+        tick_spacing: u16,
+        sqrt_price: u128,
+        bumps: &InitializeConcentratedBumps,
+    ) -> Result<()> {
+        require!(fee < MAX_FEE, AmmError::InvalidFeeAmount);
+        require!(tick_spacing > 0 && tick_spacing as i32 <= MAX_TICK, AmmError::InvalidTickSpacing);
+        require!(
+            sqrt_price >= sqrt_price_at_tick(MIN_TICK)? && sqrt_price < sqrt_price_at_tick(MAX_TICK)?,
+            AmmError::InvalidSqrtPrice
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+
+        self.config.set_inner(Config {
+            seed,
+            authority,
+            mint_x: self.mint_x.key(),
+            mint_y: self.mint_y.key(),
+            fee,
+            locked: false,
+            config_bump: bumps.config,
+            lp_bump: 0,
+            protocol_fee: 0,
+            protocol_fees_x: 0,
+            protocol_fees_y: 0,
+            price_x_cumulative: 0,
+            price_y_cumulative: 0,
+            last_update: now,
+            curve_type: CurveType::Concentrated,
+            amp_initial: 0,
+            amp_target: 0,
+            ramp_start: now,
+            ramp_end: now,
+            flash_loan: None,
+        });
+
+        self.pool.set_inner(ConcentratedPool {
+            config: self.config.key(),
+            tick_spacing,
+            tick_current: tick_at_sqrt_price(sqrt_price, MIN_TICK, MAX_TICK)?,
+            sqrt_price,
+            liquidity: 0,
+            fee_growth_global_x: 0,
+            fee_growth_global_y: 0,
+            bump: bumps.pool,
+        });
+
+        self.oracle.set_inner(Oracle::new(self.config.key(), bumps.oracle, now));
+
+        self.pool_stats.set_inner(PoolStats {
+            config: self.config.key(),
+            volume_x: 0,
+            volume_y: 0,
+            fees_x: 0,
+            fees_y: 0,
+            swap_count: 0,
+            bump: bumps.pool_stats,
+        });
+
+        self.pair.set_inner(PoolPair {
+            config: self.config.key(),
+            mint_x: self.mint_x.key(),
+            mint_y: self.mint_y.key(),
+            fee,
+            index: self.registry.pool_count,
+            payer: self.initializer.key(),
+            bump: bumps.pair,
+        });
+
+        self.pool_index.set_inner(PoolIndex {
+            config: self.config.key(),
+            bump: bumps.pool_index,
+        });
+
+        self.registry.bump = bumps.registry;
+        self.registry.pool_count = self.registry.pool_count
+            .checked_add(1)
+            .ok_or(AmmError::Overflow)?;
+
+        emit!(PoolInitialized {
+            config: self.config.key(),
+            initializer: self.initializer.key(),
+            authority,
+            mint_x: self.mint_x.key(),
+            mint_y: self.mint_y.key(),
+            mint_lp: None,
+            fee,
+            curve_type: CurveType::Concentrated,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
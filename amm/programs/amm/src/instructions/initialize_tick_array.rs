@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::{MAX_TICK, MIN_TICK},
+    error::AmmError,
+    state::{ConcentratedPool, Config, TickArray},
+};
+
+#[derive(Accounts)]
+#[instruction(start_tick: i32)]
+pub struct InitializeTickArray<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.config_bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        has_one = config,
+        seeds = [b"concentrated", config.key().as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, ConcentratedPool>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + std::mem::size_of::<TickArray>(),
+        seeds = [b"tick_array", config.key().as_ref(), start_tick.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub tick_array: AccountLoader<'info, TickArray>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeTickArray<'info> {
+
+    // Anyone can pay for the tick arrays a position or a swap needs
+    pub fn init(&mut self, start_tick: i32) -> Result<()> {
+        // Every tick of the array has to be inside the tick range
+        let span = self.pool.tick_array_span();
+        require!(start_tick % span == 0, AmmError::InvalidTickArray);
+        require!(
+            start_tick >= MIN_TICK && start_tick + (span - 1) <= MAX_TICK,
+            AmmError::InvalidTickArray
+        );
+
+        let mut tick_array = self.tick_array.load_init()?;
+        tick_array.config = self.config.key();
+        tick_array.start_tick = start_tick;
+
+        Ok(())
+    }
+}
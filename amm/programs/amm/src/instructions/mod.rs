@@ -1,9 +1,41 @@
 pub mod initialize;
 pub mod deposit;
 pub mod withdraw;
+pub mod deposit_single;
+pub mod withdraw_single;
 pub mod swap;
+pub mod update_config;
+pub mod update_fee;
+pub mod collect_protocol_fees;
+pub mod get_twap;
+pub mod initialize_concentrated;
+pub mod initialize_tick_array;
+pub mod open_position;
+pub mod modify_position;
+pub mod close_position;
+pub mod swap_concentrated;
+pub mod flash_loan;
+pub mod initialize_farm;
+pub mod fund_farm;
+pub mod stake_lp;
 
 pub use initialize::*;
 pub use deposit::*;
 pub use withdraw::*;
+pub use deposit_single::*;
+pub use withdraw_single::*;
 pub use swap::*;
+pub use update_config::*;
+pub use update_fee::*;
+pub use collect_protocol_fees::*;
+pub use get_twap::*;
+pub use initialize_concentrated::*;
+pub use initialize_tick_array::*;
+pub use open_position::*;
+pub use modify_position::*;
+pub use close_position::*;
+pub use swap_concentrated::*;
+pub use flash_loan::*;
+pub use initialize_farm::*;
+pub use fund_farm::*;
+pub use stake_lp::*;
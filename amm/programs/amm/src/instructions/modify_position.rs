@@ -0,0 +1,251 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+use crate::{
+    curves::concentrated::liquidity_amounts,
+    error::AmmError,
+    state::{ConcentratedPool, Config, Position, Tick, TickArray},
+    utils::{amount_after_transfer_fee, amount_before_transfer_fee},
+};
+
+#[derive(Accounts)]
+pub struct ModifyPosition<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(mint::token_program = token_program_x)]
+    pub mint_x: InterfaceAccount<'info, Mint>,
+    #[account(mint::token_program = token_program_y)]
+    pub mint_y: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        has_one = mint_x,
+        has_one = mint_y,
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.config_bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        has_one = config,
+        seeds = [b"concentrated", config.key().as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, ConcentratedPool>,
+
+    #[account(
+        mut,
+        has_one = owner,
+        has_one = config,
+    )]
+    pub position: Account<'info, Position>,
+
+    // Holds position.tick_lower, can be the same account as tick_array_upper
+    #[account(mut)]
+    pub tick_array_lower: AccountLoader<'info, TickArray>,
+
+    // Holds position.tick_upper
+    #[account(mut)]
+    pub tick_array_upper: AccountLoader<'info, TickArray>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_x,
+        associated_token::authority = config,
+        associated_token::token_program = token_program_x,
+    )]
+    pub vault_x: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_y,
+        associated_token::authority = config,
+        associated_token::token_program = token_program_y,
+    )]
+    pub vault_y: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_x,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program_x,
+    )]
+    pub owner_x: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_y,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program_y,
+    )]
+    pub owner_y: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program_x: Interface<'info, TokenInterface>,
+    pub token_program_y: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ModifyPosition<'info> {
+
+    pub fn increase_liquidity(&mut self, liquidity: u128, max_x: u64, max_y: u64) -> Result<()> {
+        require!(!self.config.locked, AmmError::StillLocked);
+        require!(liquidity != 0, AmmError::ZeroAmount);
+
+        let delta = i128::try_from(liquidity).map_err(|_| AmmError::Overflow)?;
+        self.update_position(delta)?;
+
+        let (x, y) = liquidity_amounts(
+            self.pool.sqrt_price,
+            self.pool.tick_current,
+            self.position.tick_lower,
+            self.position.tick_upper,
+            liquidity,
+            true,
+        )?;
+
+        // Gross up so the vaults receive what the range needs after transfer fees
+        let x = amount_before_transfer_fee(&self.mint_x, x)?;
+        let y = amount_before_transfer_fee(&self.mint_y, y)?;
+        require!(x <= max_x && y <= max_y, AmmError::SlippageExceeded);
+
+        self.deposit_tokens(true, x)?;
+        self.deposit_tokens(false, y)?;
+
+        Ok(())
+    }
+
+    pub fn decrease_liquidity(&mut self, liquidity: u128, min_x: u64, min_y: u64) -> Result<()> {
+        require!(!self.config.locked, AmmError::StillLocked);
+        require!(liquidity != 0, AmmError::ZeroAmount);
+
+        let delta = i128::try_from(liquidity).map_err(|_| AmmError::Overflow)?;
+        self.update_position(-delta)?;
+
+        let (x, y) = liquidity_amounts(
+            self.pool.sqrt_price,
+            self.pool.tick_current,
+            self.position.tick_lower,
+            self.position.tick_upper,
+            liquidity,
+            false,
+        )?;
+
+        // Slippage bounds apply to what reaches the owner after transfer fees
+        let received_x = amount_after_transfer_fee(&self.mint_x, x)?;
+        let received_y = amount_after_transfer_fee(&self.mint_y, y)?;
+        require!(min_x <= received_x && min_y <= received_y, AmmError::SlippageExceeded);
+
+        self.withdraw_tokens(true, x)?;
+        self.withdraw_tokens(false, y)?;
+
+        Ok(())
+    }
+
+    pub fn collect_fees(&mut self) -> Result<()> {
+        self.update_position(0)?;
+
+        let (fees_x, fees_y) = (self.position.fees_owed_x, self.position.fees_owed_y);
+        self.position.fees_owed_x = 0;
+        self.position.fees_owed_y = 0;
+
+        self.withdraw_tokens(true, fees_x)?;
+        self.withdraw_tokens(false, fees_y)?;
+
+        Ok(())
+    }
+
+    // Applies `liquidity_delta` to the position's ticks, the position itself and the pool
+    // liquidity if the range is active, booking the fees the position earned so far
+    fn update_position(&mut self, liquidity_delta: i128) -> Result<()> {
+        let (tick_lower, tick_upper) = (self.position.tick_lower, self.position.tick_upper);
+        let (tick_current, spacing) = (self.pool.tick_current, self.pool.tick_spacing);
+        let (global_x, global_y) = (self.pool.fee_growth_global_x, self.pool.fee_growth_global_y);
+
+        let update_tick = |loader: &AccountLoader<'info, TickArray>, tick: i32, is_upper: bool| -> Result<(bool, Tick)> {
+            let mut array = loader.load_mut()?;
+            require_keys_eq!(array.config, self.config.key(), AmmError::InvalidTickArray);
+
+            let entry = array.tick_mut(tick, spacing).ok_or(AmmError::InvalidTickArray)?;
+            let flipped = entry
+                .update(tick, tick_current, liquidity_delta, is_upper, global_x, global_y)
+                .ok_or(AmmError::InsufficientLiquidity)?;
+            Ok((flipped, *entry))
+        };
+
+        let (flipped_lower, lower) = update_tick(&self.tick_array_lower, tick_lower, false)?;
+        let (flipped_upper, upper) = update_tick(&self.tick_array_upper, tick_upper, true)?;
+
+        let (inside_x, inside_y) = self.pool.fee_growth_inside(tick_lower, &lower, tick_upper, &upper);
+        self.position.update(liquidity_delta, inside_x, inside_y)?;
+
+        // Ticks no position uses anymore start from scratch if they are used again
+        if liquidity_delta < 0 {
+            if flipped_lower {
+                let mut array = self.tick_array_lower.load_mut()?;
+                array.tick_mut(tick_lower, spacing).ok_or(AmmError::InvalidTickArray)?.clear();
+            }
+            if flipped_upper {
+                let mut array = self.tick_array_upper.load_mut()?;
+                array.tick_mut(tick_upper, spacing).ok_or(AmmError::InvalidTickArray)?.clear();
+            }
+        }
+
+        if tick_lower <= tick_current && tick_current < tick_upper {
+            self.pool.liquidity = self.pool.liquidity
+                .checked_add_signed(liquidity_delta)
+                .ok_or(AmmError::InsufficientLiquidity)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn deposit_tokens(&self, is_x: bool, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let (from, to, mint, token_program) = match is_x {
+            true => (&self.owner_x, &self.vault_x, &self.mint_x, &self.token_program_x),
+            false => (&self.owner_y, &self.vault_y, &self.mint_y, &self.token_program_y),
+        };
+
+        let cpi_accounts = TransferChecked {
+            from: from.to_account_info(),
+            to: to.to_account_info(),
+            mint: mint.to_account_info(),
+            authority: self.owner.to_account_info(),
+        };
+
+        let ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts);
+        transfer_checked(ctx, amount, mint.decimals)
+    }
+
+    pub fn withdraw_tokens(&self, is_x: bool, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let (from, to, mint, token_program) = match is_x {
+            true => (&self.vault_x, &self.owner_x, &self.mint_x, &self.token_program_x),
+            false => (&self.vault_y, &self.owner_y, &self.mint_y, &self.token_program_y),
+        };
+
+        let cpi_accounts = TransferChecked {
+            from: from.to_account_info(),
+            to: to.to_account_info(),
+            mint: mint.to_account_info(),
+            authority: self.config.to_account_info(),
+        };
+
+        let seeds = &[
+            &b"config"[..],
+            &self.config.seed.to_le_bytes(),
+            &[self.config.config_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer_seeds);
+        transfer_checked(ctx, amount, mint.decimals)
+    }
+}
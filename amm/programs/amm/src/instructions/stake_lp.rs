@@ -0,0 +1,268 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+use crate::{
+    error::AmmError,
+    events::{LpStaked, LpUnstaked, RewardsClaimed, RewardsForfeited},
+    state::{Config, Farm, FarmStake},
+};
+
+#[derive(Accounts)]
+pub struct StakeLp<'info>{
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.config_bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [b"lp", config.key().as_ref()],
+        bump = config.lp_bump,
+    )]
+    pub mint_lp: Box<InterfaceAccount<'info, Mint>>,
+
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        has_one = config,
+        has_one = reward_mint,
+        seeds = [b"farm", config.key().as_ref(), reward_mint.key().as_ref()],
+        bump = farm.bump,
+    )]
+    pub farm: Account<'info, Farm>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + FarmStake::INIT_SPACE,
+        seeds = [b"stake", farm.key().as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    pub stake: Account<'info, FarmStake>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_lp,
+        associated_token::authority = farm,
+        associated_token::token_program = token_program,
+    )]
+    pub lp_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = reward_mint,
+        associated_token::authority = farm,
+        associated_token::token_program = reward_token_program,
+    )]
+    pub reward_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = mint_lp,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    pub owner_lp: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = reward_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = reward_token_program,
+    )]
+    pub owner_reward: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub reward_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> StakeLp<'info>{
+
+    pub fn stake_lp(&mut self, amount: u64, bumps: &StakeLpBumps) -> Result<()> {
+        require!(amount > 0, AmmError::ZeroAmount);
+        let now = self.update_stake(bumps)?;
+
+        self.stake.amount = self.stake.amount.checked_add(amount).ok_or(AmmError::Overflow)?;
+        self.farm.total_staked = self.farm.total_staked.checked_add(amount).ok_or(AmmError::Overflow)?;
+
+        let cpi_program = self.token_program.to_account_info();
+
+        let accounts = TransferChecked {
+            from: self.owner_lp.to_account_info(),
+            to: self.lp_vault.to_account_info(),
+            mint: self.mint_lp.to_account_info(),
+            authority: self.owner.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(cpi_program, accounts);
+
+        transfer_checked(cpi_ctx, amount, self.mint_lp.decimals)?;
+
+        emit!(LpStaked {
+            farm: self.farm.key(),
+            owner: self.owner.key(),
+            amount,
+            total_staked: self.farm.total_staked,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    // Rewards earned so far stay booked on the stake account until claimed
+    pub fn unstake_lp(&mut self, amount: u64, bumps: &StakeLpBumps) -> Result<()> {
+        require!(amount > 0, AmmError::ZeroAmount);
+        let now = self.update_stake(bumps)?;
+
+        self.stake.amount = self.stake.amount.checked_sub(amount).ok_or(AmmError::InsufficientStake)?;
+        self.farm.total_staked = self.farm.total_staked.checked_sub(amount).ok_or(AmmError::Underflow)?;
+
+        self.withdraw_from_farm(
+            &self.lp_vault,
+            &self.owner_lp,
+            &self.mint_lp,
+            &self.token_program,
+            amount,
+        )?;
+
+        emit!(LpUnstaked {
+            farm: self.farm.key(),
+            owner: self.owner.key(),
+            amount,
+            total_staked: self.farm.total_staked,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    // Takes the whole stake out without booking its rewards, for when doing so fails. The
+    // rewards owed are set aside for the next fund_farm and the unbooked ones are lost.
+    pub fn emergency_unstake_lp(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        self.farm.update(now);
+
+        let amount = self.stake.amount;
+        require!(amount > 0, AmmError::InsufficientStake);
+        let forfeited = self.stake.rewards_owed;
+
+        self.stake.amount = 0;
+        self.stake.rewards_owed = 0;
+        self.stake.reward_per_share_paid = self.farm.reward_per_share;
+        self.farm.total_staked = self.farm.total_staked.checked_sub(amount).ok_or(AmmError::Underflow)?;
+        self.farm.set_aside(forfeited as u128);
+
+        self.withdraw_from_farm(
+            &self.lp_vault,
+            &self.owner_lp,
+            &self.mint_lp,
+            &self.token_program,
+            amount,
+        )?;
+
+        emit!(LpUnstaked {
+            farm: self.farm.key(),
+            owner: self.owner.key(),
+            amount,
+            total_staked: self.farm.total_staked,
+            timestamp: now,
+        });
+        emit!(RewardsForfeited {
+            farm: self.farm.key(),
+            owner: self.owner.key(),
+            amount: forfeited,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    pub fn claim_rewards(&mut self, bumps: &StakeLpBumps) -> Result<()> {
+        let now = self.update_stake(bumps)?;
+
+        let amount = self.stake.rewards_owed;
+        require!(amount > 0, AmmError::ZeroAmount);
+        self.stake.rewards_owed = 0;
+
+        self.withdraw_from_farm(
+            &self.reward_vault,
+            &self.owner_reward,
+            &self.reward_mint,
+            &self.reward_token_program,
+            amount,
+        )?;
+
+        emit!(RewardsClaimed {
+            farm: self.farm.key(),
+            owner: self.owner.key(),
+            amount,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    // Brings the farm up to date and books the owner's rewards, setting up the stake account
+    // on the first call. Returns the current time.
+    fn update_stake(&mut self, bumps: &StakeLpBumps) -> Result<i64> {
+        let now = Clock::get()?.unix_timestamp;
+        self.farm.update(now);
+
+        if self.stake.farm == Pubkey::default() {
+            self.stake.set_inner(FarmStake {
+                owner: self.owner.key(),
+                farm: self.farm.key(),
+                amount: 0,
+                reward_per_share_paid: self.farm.reward_per_share,
+                rewards_owed: 0,
+                bump: bumps.stake,
+            });
+        }
+
+        self.stake.update(self.farm.reward_per_share)?;
+
+        Ok(now)
+    }
+
+    fn withdraw_from_farm(
+        &self,
+        from: &InterfaceAccount<'info, TokenAccount>,
+        to: &InterfaceAccount<'info, TokenAccount>,
+        mint: &InterfaceAccount<'info, Mint>,
+        token_program: &Interface<'info, TokenInterface>,
+        amount: u64,
+    ) -> Result<()> {
+        let accounts = TransferChecked {
+            from: from.to_account_info(),
+            to: to.to_account_info(),
+            mint: mint.to_account_info(),
+            authority: self.farm.to_account_info(),
+        };
+
+        let config = self.config.key();
+        let reward_mint = self.reward_mint.key();
+        let seeds = &[
+            &b"farm"[..],
+            config.as_ref(),
+            reward_mint.as_ref(),
+            &[self.farm.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), accounts, signer_seeds);
+
+        transfer_checked(cpi_ctx, amount, mint.decimals)?;
+
+        Ok(())
+    }
+}
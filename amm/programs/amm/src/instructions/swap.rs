@@ -1,15 +1,16 @@
+use std::collections::BTreeSet;
+
 use anchor_lang::prelude::*;
 use anchor_spl::{
-    associated_token::AssociatedToken,
-    token::{Mint, Token, TokenAccount, Transfer, transfer}
-};
-use constant_product_curve::{
-    ConstantProduct,
-    LiquidityPair
+    associated_token::{get_associated_token_address_with_program_id, AssociatedToken},
+    token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked}
 };
 use crate::{
+    curves,
     error::AmmError,
-    state::Config,
+    events::Swapped,
+    state::{Config, Oracle, PoolStats},
+    utils::{amount_after_transfer_fee, amount_before_transfer_fee},
 };
 
 #[derive(Accounts)]
@@ -17,26 +18,31 @@ pub struct Swap<'info>{
     #[account(mut)]
     pub user: Signer<'info>,
 
-    pub mint_x: Account<'info, Mint>,
-    pub mint_y: Account<'info, Mint>,
+    #[account(mint::token_program = token_program_x)]
+    pub mint_x: InterfaceAccount<'info, Mint>,
+    #[account(mint::token_program = token_program_y)]
+    pub mint_y: InterfaceAccount<'info, Mint>,
 
     #[account(
         init_if_needed,
         payer = user,
         associated_token::mint = mint_x,
         associated_token::authority = user,
+        associated_token::token_program = token_program_x,
     )]
-    pub user_x: Account<'info, TokenAccount>,
+    pub user_x: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         init_if_needed,
         payer = user,
         associated_token::mint = mint_y,
         associated_token::authority = user,
+        associated_token::token_program = token_program_y,
     )]
-    pub user_y: Account<'info, TokenAccount>,
+    pub user_y: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
+        mut,
         has_one = mint_x,
         has_one = mint_y,
         seeds = [b"config", config.seed.to_le_bytes().as_ref()],
@@ -44,86 +50,227 @@ pub struct Swap<'info>{
     )]
     pub config: Account<'info, Config>,
 
+    #[account(
+        mut,
+        has_one = config,
+        seeds = [b"oracle", config.key().as_ref()],
+        bump = oracle.bump,
+    )]
+    pub oracle: Account<'info, Oracle>,
+
+    #[account(
+        mut,
+        has_one = config,
+        seeds = [b"stats", config.key().as_ref()],
+        bump = pool_stats.bump,
+    )]
+    pub pool_stats: Account<'info, PoolStats>,
+
     #[account(
         mut,
         associated_token::mint = mint_x,
         associated_token::authority = config,
+        associated_token::token_program = token_program_x,
     )]
-    pub vault_x: Account<'info, TokenAccount>,
+    pub vault_x: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
         associated_token::mint = mint_y,
         associated_token::authority = config,
+        associated_token::token_program = token_program_y,
     )]
-    pub vault_y: Account<'info, TokenAccount>,
+    pub vault_y: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program_x: Interface<'info, TokenInterface>,
+    pub token_program_y: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> Swap <'info>{
-    pub fn swap(&mut self, is_x: bool, amount: u64, min: u64) -> Result<()> {
-        require!(self.config.locked == false, AmmError::StillLocked);
-        require!(amount > 0, AmmError::IncorrectAmmount);
-
-        let mut curve = ConstantProduct::init(
-            self.vault_x.amount,
-             self.vault_y.amount, 
-             self.vault_x.amount, 
-             self.config.fee,
-            None,
-        ).unwrap();
-
-        let p = match is_x {
-            true => LiquidityPair::X,
-            false => LiquidityPair::Y,
+    pub fn swap(&mut self, is_x: bool, amount_in: u64, min_amount_out: u64) -> Result<()> {
+        let amount_out = self.execute_swap(is_x, amount_in)?;
+        require!(amount_out >= min_amount_out, AmmError::SlippageExceeded);
+
+        Ok(())
+    }
+
+    // Receives exactly `amount_out`, paying in whatever the curve and fee ask for up to `max_amount_in`
+    pub fn swap_exact_out(&mut self, is_x: bool, amount_out: u64, max_amount_in: u64) -> Result<()> {
+        let amount_in = self.execute_swap_exact_out(is_x, amount_out)?;
+        require!(amount_in <= max_amount_in, AmmError::SlippageExceeded);
+
+        Ok(())
+    }
+
+    // Swaps through this pool and then every pool in `remaining_accounts`, each given as the
+    // `SwapHop` account list. The output of a hop is the input of the next one, and only the
+    // final output is checked against `min`.
+    pub fn swap_route(
+        &mut self,
+        is_x: bool,
+        amount: u64,
+        min: u64,
+        program_id: &Pubkey,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        let mut amount_out = self.execute_swap(is_x, amount)?;
+        let (mut mint_in, mut user_in, mut token_program_in) = match is_x {
+            true => (self.mint_y.clone(), self.user_y.clone(), self.token_program_y.clone()),
+            false => (self.mint_x.clone(), self.user_x.clone(), self.token_program_x.clone()),
         };
 
-        let res = curve.swap(p, amount, min).unwrap();
+        let mut accounts = remaining_accounts;
+        while !accounts.is_empty() {
+            let mut bumps = SwapHopBumps::default();
+            let mut reallocs = BTreeSet::new();
+            let mut hop = SwapHop::try_accounts(program_id, &mut accounts, &[], &mut bumps, &mut reallocs)?;
+
+            // This pool's config is only written back once the instruction ends
+            require_keys_neq!(hop.config.key(), self.config.key(), AmmError::InvalidRoute);
+
+            amount_out = hop.execute_swap(&self.user, &mint_in, &user_in, &token_program_in, amount_out)?;
+            hop.exit(program_id)?;
+
+            (mint_in, user_in, token_program_in) = (hop.mint_out, hop.user_out, hop.token_program_out);
+        }
+
+        require!(amount_out >= min, AmmError::SlippageExceeded);
+
+        Ok(())
+    }
+
+    // Runs one swap through this pool and returns what reached the user
+    pub fn execute_swap(&mut self, is_x: bool, amount: u64) -> Result<u64> {
+        require!(!self.config.locked, AmmError::StillLocked);
+        require!(amount > 0, AmmError::ZeroAmount);
+
+        let (x, y) = self.config.reserves(self.vault_x.amount, self.vault_y.amount)?;
+        let now = Clock::get()?.unix_timestamp;
+        self.oracle.update(&mut self.config, x, y, now);
+
+        let (mint_in, mint_out) = match is_x {
+            true => (&self.mint_x, &self.mint_y),
+            false => (&self.mint_y, &self.mint_x),
+        };
+
+        // The curve only gets credited what reaches the vault after the input transfer fee
+        let amount_in = amount_after_transfer_fee(mint_in, amount)?;
+        let res = curves::swap(&self.config, x, y, is_x, amount_in, now)?;
+
+        require!(res.amount_in != 0, AmmError::ZeroAmount);
+        require!(res.amount_out != 0, AmmError::ZeroOutput);
+
+        // and the user is paid out net of the output transfer fee
+        let amount_out = amount_after_transfer_fee(mint_out, res.amount_out)?;
+        require!(amount_out != 0, AmmError::ZeroOutput);
+
+        self.config.accrue_protocol_fee(is_x, res.fee)?;
+
+        self.deposit_tokens(is_x, amount)?;
+        self.withdraw_tokens(is_x, res.amount_out)?;
+        self.record_swap(is_x, amount, res.amount_out, res.fee, (x, y), now)?;
+
+        Ok(amount_out)
+    }
+
+    // Runs one swap through this pool that delivers exactly `amount_out` to the user and
+    // returns what the user paid in
+    pub fn execute_swap_exact_out(&mut self, is_x: bool, amount_out: u64) -> Result<u64> {
+        require!(!self.config.locked, AmmError::StillLocked);
+        require!(amount_out > 0, AmmError::ZeroAmount);
+
+        let (x, y) = self.config.reserves(self.vault_x.amount, self.vault_y.amount)?;
+        let now = Clock::get()?.unix_timestamp;
+        self.oracle.update(&mut self.config, x, y, now);
+
+        let (mint_in, mint_out) = match is_x {
+            true => (&self.mint_x, &self.mint_y),
+            false => (&self.mint_y, &self.mint_x),
+        };
+
+        // Work back from what the user receives to what the vaults have to move
+        let withdraw = amount_before_transfer_fee(mint_out, amount_out)?;
+        let res = curves::swap_exact_out(&self.config, x, y, is_x, withdraw, now)?;
+        let amount_in = amount_before_transfer_fee(mint_in, res.amount_in)?;
+
+        self.config.accrue_protocol_fee(is_x, res.fee)?;
+
+        self.deposit_tokens(is_x, amount_in)?;
+        self.withdraw_tokens(is_x, res.amount_out)?;
+        self.record_swap(is_x, amount_in, res.amount_out, res.fee, (x, y), now)?;
+
+        Ok(amount_in)
+    }
+
+    // Updates the pool stats and emits the swap with the reserves around it
+    fn record_swap(
+        &mut self,
+        is_x: bool,
+        amount_in: u64,
+        amount_out: u64,
+        fee: u64,
+        (reserve_x_before, reserve_y_before): (u64, u64),
+        now: i64,
+    ) -> Result<()> {
+        self.pool_stats.record_swap(is_x, amount_in, amount_out, fee);
 
-        require!(res.deposit != 0, AmmError::IncorrectAmmount);
-        require!(res.withdraw != 0, AmmError::IncorrectAmmount);
+        self.vault_x.reload()?;
+        self.vault_y.reload()?;
+        let (reserve_x_after, reserve_y_after) = self.config.reserves(self.vault_x.amount, self.vault_y.amount)?;
 
-        self.deposit_tokens(is_x, res.deposit)?;
-        self.withdraw_tokens(is_x, res.withdraw)?;
+        emit!(Swapped {
+            config: self.config.key(),
+            user: self.user.key(),
+            is_x,
+            amount_in,
+            amount_out,
+            fee,
+            reserve_x_before,
+            reserve_y_before,
+            reserve_x_after,
+            reserve_y_after,
+            timestamp: now,
+        });
 
         Ok(())
     }
 
     pub fn deposit_tokens(&mut self, is_x: bool, amount: u64) -> Result<()> {
-        let (from, to) = match is_x {
-            true => (self.user_x.to_account_info() , self.vault_x.to_account_info()),
-            false => (self.user_y.to_account_info(), self.vault_y.to_account_info()),
+        let (from, to, mint, token_program) = match is_x {
+            true => (&self.user_x , &self.vault_x, &self.mint_x, &self.token_program_x),
+            false => (&self.user_y, &self.vault_y, &self.mint_y, &self.token_program_y),
         };
 
-        let cpi_program = self.token_program.to_account_info();
+        let cpi_program = token_program.to_account_info();
 
-        let accounts = Transfer {
+        let accounts = TransferChecked {
             from: from.to_account_info(),
             to: to.to_account_info(),
+            mint: mint.to_account_info(),
             authority: self.user.to_account_info(),
         };
 
         let cpi_ctx = CpiContext::new(cpi_program, accounts);
 
-        transfer(cpi_ctx, amount)?;
+        transfer_checked(cpi_ctx, amount, mint.decimals)?;
 
         Ok(())
     }
 
     pub fn withdraw_tokens(&mut self, is_x: bool, amount: u64) -> Result<()> {
-        let (from, to) = match is_x {
-            true => (self.vault_y.to_account_info() , self.user_y.to_account_info()),
-            false => (self.vault_x.to_account_info(), self.user_x.to_account_info()),
+        let (from, to, mint, token_program) = match is_x {
+            true => (&self.vault_y , &self.user_y, &self.mint_y, &self.token_program_y),
+            false => (&self.vault_x, &self.user_x, &self.mint_x, &self.token_program_x),
         };
 
-        let cpi_program = self.token_program.to_account_info();
+        let cpi_program = token_program.to_account_info();
 
-        let accounts = Transfer {
+        let accounts = TransferChecked {
             from: from.to_account_info(),
             to: to.to_account_info(),
+            mint: mint.to_account_info(),
             authority: self.config.to_account_info(),
         };
 
@@ -136,8 +283,143 @@ impl<'info> Swap <'info>{
 
         let cpi_ctx = CpiContext::new_with_signer(cpi_program, accounts, signer_seeds);
 
-        transfer(cpi_ctx, amount)?;
+        transfer_checked(cpi_ctx, amount, mint.decimals)?;
 
         Ok(())
     }
-}
\ No newline at end of file
+}
+// One pool of a `swap_route` after the first. It only takes what the swap moves: the input
+// token, user account and program are the output ones of the previous hop, and the user
+// signs once for the whole route. That is five new accounts per hop, so a route through
+// four pools still fits in a transaction without an address lookup table. Hops don't
+// update their pool's oracle or PoolStats, the accumulators in the config keep the TWAP
+// right and the Swapped events carry the volume.
+#[derive(Accounts)]
+pub struct SwapHop<'info>{
+    #[account(
+        mut,
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.config_bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub vault_in: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub vault_out: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mint::token_program = token_program_out)]
+    pub mint_out: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = mint_out,
+        token::token_program = token_program_out,
+    )]
+    pub user_out: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program_out: Interface<'info, TokenInterface>,
+}
+
+impl<'info> SwapHop <'info>{
+    // Swaps `amount` of `mint_in` sent from `user_in` and returns what reached `user_out`
+    pub fn execute_swap(
+        &mut self,
+        user: &Signer<'info>,
+        mint_in: &InterfaceAccount<'info, Mint>,
+        user_in: &InterfaceAccount<'info, TokenAccount>,
+        token_program_in: &Interface<'info, TokenInterface>,
+        amount: u64,
+    ) -> Result<u64> {
+        require!(!self.config.locked, AmmError::StillLocked);
+        require!(amount > 0, AmmError::ZeroAmount);
+        require_keys_eq!(self.user_out.owner, user.key(), AmmError::InvalidRoute);
+
+        let is_x = match (mint_in.key(), self.mint_out.key()) {
+            (a, b) if a == self.config.mint_x && b == self.config.mint_y => true,
+            (a, b) if a == self.config.mint_y && b == self.config.mint_x => false,
+            _ => return err!(AmmError::InvalidRoute),
+        };
+
+        // The vaults are the config's associated token accounts, anything else could fake the reserves
+        let config = self.config.key();
+        require_keys_eq!(
+            self.vault_in.key(),
+            get_associated_token_address_with_program_id(&config, &mint_in.key(), &token_program_in.key()),
+            AmmError::InvalidRoute
+        );
+        require_keys_eq!(
+            self.vault_out.key(),
+            get_associated_token_address_with_program_id(&config, &self.mint_out.key(), &self.token_program_out.key()),
+            AmmError::InvalidRoute
+        );
+
+        let (x, y) = self.reserves(is_x)?;
+        let now = Clock::get()?.unix_timestamp;
+        self.config.update_price_cumulative(x, y, now);
+
+        let amount_in = amount_after_transfer_fee(mint_in, amount)?;
+        let res = curves::swap(&self.config, x, y, is_x, amount_in, now)?;
+
+        require!(res.amount_in != 0, AmmError::ZeroAmount);
+        require!(res.amount_out != 0, AmmError::ZeroOutput);
+
+        let amount_out = amount_after_transfer_fee(&self.mint_out, res.amount_out)?;
+        require!(amount_out != 0, AmmError::ZeroOutput);
+
+        self.config.accrue_protocol_fee(is_x, res.fee)?;
+
+        let accounts = TransferChecked {
+            from: user_in.to_account_info(),
+            to: self.vault_in.to_account_info(),
+            mint: mint_in.to_account_info(),
+            authority: user.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(token_program_in.to_account_info(), accounts);
+        transfer_checked(cpi_ctx, amount, mint_in.decimals)?;
+
+        let accounts = TransferChecked {
+            from: self.vault_out.to_account_info(),
+            to: self.user_out.to_account_info(),
+            mint: self.mint_out.to_account_info(),
+            authority: self.config.to_account_info(),
+        };
+        let seeds = &[
+            &b"config"[..],
+            &self.config.seed.to_le_bytes(),
+            &[self.config.config_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program_out.to_account_info(), accounts, signer_seeds);
+        transfer_checked(cpi_ctx, res.amount_out, self.mint_out.decimals)?;
+
+        self.vault_in.reload()?;
+        self.vault_out.reload()?;
+        self.user_out.reload()?;
+        let (reserve_x_after, reserve_y_after) = self.reserves(is_x)?;
+
+        emit!(Swapped {
+            config,
+            user: user.key(),
+            is_x,
+            amount_in: amount,
+            amount_out: res.amount_out,
+            fee: res.fee,
+            reserve_x_before: x,
+            reserve_y_before: y,
+            reserve_x_after,
+            reserve_y_after,
+            timestamp: now,
+        });
+
+        Ok(amount_out)
+    }
+
+    fn reserves(&self, is_x: bool) -> Result<(u64, u64)> {
+        match is_x {
+            true => self.config.reserves(self.vault_in.amount, self.vault_out.amount),
+            false => self.config.reserves(self.vault_out.amount, self.vault_in.amount),
+        }
+    }
+}
@@ -0,0 +1,316 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked}
+};
+use crate::{
+    constants::{MAX_TICK, MIN_TICK},
+    curves::concentrated::{compute_swap_step, mul_div, sqrt_price_at_tick, tick_at_sqrt_price, Q64},
+    error::AmmError,
+    events::Swapped,
+    state::{ConcentratedPool, Config, Oracle, PoolStats, TickArray},
+    utils::amount_after_transfer_fee,
+};
+
+#[derive(Accounts)]
+pub struct SwapConcentrated<'info>{
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mint::token_program = token_program_x)]
+    pub mint_x: InterfaceAccount<'info, Mint>,
+    #[account(mint::token_program = token_program_y)]
+    pub mint_y: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = mint_x,
+        associated_token::authority = user,
+        associated_token::token_program = token_program_x,
+    )]
+    pub user_x: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = mint_y,
+        associated_token::authority = user,
+        associated_token::token_program = token_program_y,
+    )]
+    pub user_y: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        has_one = mint_x,
+        has_one = mint_y,
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.config_bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        has_one = config,
+        seeds = [b"concentrated", config.key().as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, ConcentratedPool>,
+
+    #[account(
+        mut,
+        has_one = config,
+        seeds = [b"oracle", config.key().as_ref()],
+        bump = oracle.bump,
+    )]
+    pub oracle: Account<'info, Oracle>,
+
+    #[account(
+        mut,
+        has_one = config,
+        seeds = [b"stats", config.key().as_ref()],
+        bump = pool_stats.bump,
+    )]
+    pub pool_stats: Account<'info, PoolStats>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_x,
+        associated_token::authority = config,
+        associated_token::token_program = token_program_x,
+    )]
+    pub vault_x: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_y,
+        associated_token::authority = config,
+        associated_token::token_program = token_program_y,
+    )]
+    pub vault_y: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program_x: Interface<'info, TokenInterface>,
+    pub token_program_y: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SwapConcentrated <'info>{
+
+    // Swaps `amount` of token x (or y) across as many ticks as needed. `tick_arrays` are the
+    // tick arrays the price moves through, starting with the one holding the current tick.
+    pub fn swap_concentrated(
+        &mut self,
+        is_x: bool,
+        amount: u64,
+        min: u64,
+        tick_arrays: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        require!(!self.config.locked, AmmError::StillLocked);
+        require!(amount > 0, AmmError::ZeroAmount);
+
+        let tick_arrays = tick_arrays
+            .iter()
+            .map(AccountLoader::<TickArray>::try_from)
+            .collect::<Result<Vec<_>>>()?;
+        let mut start_ticks = Vec::with_capacity(tick_arrays.len());
+        for tick_array in tick_arrays.iter() {
+            let tick_array = tick_array.load()?;
+            require_keys_eq!(tick_array.config, self.config.key(), AmmError::InvalidTickArray);
+            start_ticks.push(tick_array.start_tick);
+        }
+
+        let (reserve_x_before, reserve_y_before) = self.config.reserves(self.vault_x.amount, self.vault_y.amount)?;
+        let now = Clock::get()?.unix_timestamp;
+        self.oracle.update_concentrated(&mut self.config, self.pool.sqrt_price, now);
+        let (mint_in, mint_out) = match is_x {
+            true => (&self.mint_x, &self.mint_y),
+            false => (&self.mint_y, &self.mint_x),
+        };
+
+        // Selling x moves the price of x in y down
+        let mut remaining = amount_after_transfer_fee(mint_in, amount)?;
+        let mut amount_out: u64 = 0;
+        let mut fee: u64 = 0;
+
+        while remaining > 0 {
+            let (tick_next, tick_array) = self.next_tick(&tick_arrays, &start_ticks, is_x)?;
+            let sqrt_price_target = sqrt_price_at_tick(tick_next)?;
+
+            let step = compute_swap_step(
+                self.pool.sqrt_price,
+                sqrt_price_target,
+                self.pool.liquidity,
+                remaining,
+                self.config.fee,
+            )?;
+
+            remaining -= step.amount_in + step.fee;
+            amount_out = amount_out.checked_add(step.amount_out).ok_or(AmmError::Overflow)?;
+            fee = fee.checked_add(step.fee).ok_or(AmmError::Overflow)?;
+
+            // The LPs' part of the fee is spread over the liquidity in range
+            let protocol_fee = self.config.accrue_protocol_fee(is_x, step.fee)?;
+            if self.pool.liquidity > 0 {
+                let growth = mul_div((step.fee - protocol_fee) as u128, Q64, self.pool.liquidity, false)?;
+                match is_x {
+                    true => self.pool.fee_growth_global_x = self.pool.fee_growth_global_x.wrapping_add(growth),
+                    false => self.pool.fee_growth_global_y = self.pool.fee_growth_global_y.wrapping_add(growth),
+                }
+            }
+
+            if step.sqrt_price == sqrt_price_target {
+                let tick_array = tick_array.ok_or(match tick_next {
+                    MIN_TICK | MAX_TICK => AmmError::InsufficientLiquidity,
+                    _ => AmmError::MissingTickArray,
+                })?;
+                let mut tick_array = tick_arrays[tick_array].load_mut()?;
+                let tick = tick_array
+                    .tick_mut(tick_next, self.pool.tick_spacing)
+                    .ok_or(AmmError::InvalidTickArray)?;
+
+                if tick.is_initialized() {
+                    let liquidity_net = tick.cross(self.pool.fee_growth_global_x, self.pool.fee_growth_global_y);
+                    let liquidity_delta = match is_x {
+                        true => liquidity_net.checked_neg().ok_or(AmmError::Overflow)?,
+                        false => liquidity_net,
+                    };
+                    self.pool.liquidity = self.pool.liquidity
+                        .checked_add_signed(liquidity_delta)
+                        .ok_or(AmmError::InsufficientLiquidity)?;
+                }
+
+                self.pool.tick_current = match is_x {
+                    true => tick_next - 1,
+                    false => tick_next,
+                };
+            } else {
+                let (lower, upper) = match is_x {
+                    true => (tick_next, self.pool.tick_current),
+                    false => (self.pool.tick_current, tick_next),
+                };
+                self.pool.tick_current = tick_at_sqrt_price(step.sqrt_price, lower, upper)?;
+            }
+            self.pool.sqrt_price = step.sqrt_price;
+        }
+
+        require!(amount_out != 0, AmmError::ZeroOutput);
+        require!(amount_after_transfer_fee(mint_out, amount_out)? >= min, AmmError::SlippageExceeded);
+
+        self.deposit_tokens(is_x, amount)?;
+        self.withdraw_tokens(is_x, amount_out)?;
+
+        self.pool_stats.record_swap(is_x, amount, amount_out, fee);
+
+        self.vault_x.reload()?;
+        self.vault_y.reload()?;
+        let (reserve_x_after, reserve_y_after) = self.config.reserves(self.vault_x.amount, self.vault_y.amount)?;
+
+        emit!(Swapped {
+            config: self.config.key(),
+            user: self.user.key(),
+            is_x,
+            amount_in: amount,
+            amount_out,
+            fee,
+            reserve_x_before,
+            reserve_y_before,
+            reserve_x_after,
+            reserve_y_after,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    // Next tick the price reaches going down (`is_x`) or up: the closest initialized tick, or
+    // the first one not covered by `tick_arrays`, or the end of the tick range. Also returns
+    // the index of the tick array holding it. `start_ticks` are the tick arrays' start ticks,
+    // so only the arrays actually scanned get loaded.
+    fn next_tick(
+        &self,
+        tick_arrays: &[AccountLoader<'info, TickArray>],
+        start_ticks: &[i32],
+        is_x: bool,
+    ) -> Result<(i32, Option<usize>)> {
+        let spacing = self.pool.tick_spacing as i32;
+        let span = self.pool.tick_array_span();
+        let (mut tick, step) = match is_x {
+            true => (self.pool.tick_current.div_euclid(spacing) * spacing, -spacing),
+            false => ((self.pool.tick_current.div_euclid(spacing) + 1) * spacing, spacing),
+        };
+
+        loop {
+            if tick < MIN_TICK {
+                return Ok((MIN_TICK, None));
+            }
+            if tick > MAX_TICK {
+                return Ok((MAX_TICK, None));
+            }
+
+            let index = match start_ticks.iter().position(|start| tick >= *start && tick < start + span) {
+                Some(index) => index,
+                None => return Ok((tick, None)),
+            };
+
+            let (last, initialized) = tick_arrays[index]
+                .load()?
+                .next_initialized_tick(tick, self.pool.tick_spacing, is_x);
+            match initialized {
+                true => return Ok((last, Some(index))),
+                false => tick = last + step,
+            }
+        }
+    }
+
+    pub fn deposit_tokens(&self, is_x: bool, amount: u64) -> Result<()> {
+        let (from, to, mint, token_program) = match is_x {
+            true => (&self.user_x , &self.vault_x, &self.mint_x, &self.token_program_x),
+            false => (&self.user_y, &self.vault_y, &self.mint_y, &self.token_program_y),
+        };
+
+        let cpi_program = token_program.to_account_info();
+
+        let accounts = TransferChecked {
+            from: from.to_account_info(),
+            to: to.to_account_info(),
+            mint: mint.to_account_info(),
+            authority: self.user.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(cpi_program, accounts);
+
+        transfer_checked(cpi_ctx, amount, mint.decimals)?;
+
+        Ok(())
+    }
+
+    pub fn withdraw_tokens(&self, is_x: bool, amount: u64) -> Result<()> {
+        let (from, to, mint, token_program) = match is_x {
+            true => (&self.vault_y , &self.user_y, &self.mint_y, &self.token_program_y),
+            false => (&self.vault_x, &self.user_x, &self.mint_x, &self.token_program_x),
+        };
+
+        let cpi_program = token_program.to_account_info();
+
+        let accounts = TransferChecked {
+            from: from.to_account_info(),
+            to: to.to_account_info(),
+            mint: mint.to_account_info(),
+            authority: self.config.to_account_info(),
+        };
+
+        let seeds = &[
+            &b"config"[..],
+            &self.config.seed.to_le_bytes(),
+            &[self.config.config_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, accounts, signer_seeds);
+
+        transfer_checked(cpi_ctx, amount, mint.decimals)?;
+
+        Ok(())
+    }
+}
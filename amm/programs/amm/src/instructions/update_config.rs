@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::{MAX_AMP, MAX_AMP_CHANGE, MIN_RAMP_DURATION},
+    error::AmmError,
+    events::{
+        AmpRampStarted, AuthorityTransferred, PoolLocked, PoolUnlocked, ProtocolFeeUpdated,
+    },
+    state::{Config, CurveType},
+};
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info>{
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.config_bump,
+        constraint = config.authority == Some(authority.key()) @ AmmError::InvalidAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+impl<'info> UpdateConfig<'info>{
+
+    pub fn lock(&mut self) -> Result<()>{
+        self.config.locked = true;
+
+        emit!(PoolLocked {
+            config: self.config.key(),
+            authority: self.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn unlock(&mut self) -> Result<()>{
+        self.config.locked = false;
+
+        emit!(PoolUnlocked {
+            config: self.config.key(),
+            authority: self.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn update_protocol_fee(&mut self, protocol_fee: u16) -> Result<()>{
+        let old_protocol_fee = self.config.set_protocol_fee(protocol_fee)?;
+
+        emit!(ProtocolFeeUpdated {
+            config: self.config.key(),
+            authority: self.authority.key(),
+            old_protocol_fee,
+            new_protocol_fee: protocol_fee,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    // Moves A linearly from its current value to `amp_target` until `ramp_end`
+    pub fn ramp_amp(&mut self, amp_target: u64, ramp_end: i64) -> Result<()>{
+        require!(self.config.curve_type == CurveType::StableSwap, AmmError::NotStableSwap);
+        require!(amp_target > 0 && amp_target <= MAX_AMP, AmmError::InvalidAmp);
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(ramp_end >= now + MIN_RAMP_DURATION, AmmError::InvalidRamp);
+
+        let amp_initial = self.config.amp(now);
+        require!(
+            amp_target <= amp_initial * MAX_AMP_CHANGE && amp_target * MAX_AMP_CHANGE >= amp_initial,
+            AmmError::InvalidRamp
+        );
+
+        self.config.amp_initial = amp_initial;
+        self.config.amp_target = amp_target;
+        self.config.ramp_start = now;
+        self.config.ramp_end = ramp_end;
+
+        emit!(AmpRampStarted {
+            config: self.config.key(),
+            authority: self.authority.key(),
+            amp_initial,
+            amp_target,
+            ramp_end,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    // Passing None renounces the authority for good, so a locked pool would stay locked
+    pub fn transfer_authority(&mut self, new_authority: Option<Pubkey>) -> Result<()>{
+        require!(new_authority.is_some() || !self.config.locked, AmmError::RenounceWhileLocked);
+
+        self.config.authority = new_authority;
+
+        emit!(AuthorityTransferred {
+            config: self.config.key(),
+            old_authority: self.authority.key(),
+            new_authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
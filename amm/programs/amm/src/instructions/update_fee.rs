@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::MAX_FEE,
+    error::AmmError,
+    events::FeeUpdated,
+    state::{Config, PoolPair},
+};
+
+// Moving a pool to another fee tier moves its PoolPair too, so the pair PDA keeps pointing at
+// the pool and creating the new one fails if the tier already has a pool
+#[derive(Accounts)]
+#[instruction(fee: u16)]
+pub struct UpdateFee<'info>{
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.config_bump,
+        constraint = config.authority == Some(authority.key()) @ AmmError::InvalidAuthority,
+    )]
+    pub config: Account<'info, Config>,
+
+    // Gets back the rent it paid for the old pair account
+    #[account(mut)]
+    pub payer: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        close = payer,
+        has_one = config,
+        has_one = payer,
+        seeds = [b"pair", config.mint_x.as_ref(), config.mint_y.as_ref(), config.fee.to_le_bytes().as_ref()],
+        bump = pair.bump,
+    )]
+    pub pair: Account<'info, PoolPair>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PoolPair::INIT_SPACE,
+        seeds = [b"pair", config.mint_x.as_ref(), config.mint_y.as_ref(), fee.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub new_pair: Account<'info, PoolPair>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> UpdateFee<'info>{
+
+    pub fn update_fee(&mut self, fee: u16, bumps: &UpdateFeeBumps) -> Result<()>{
+        // The curves can't take the whole input as fee
+        require!(fee < MAX_FEE, AmmError::InvalidFeeAmount);
+
+        let old_fee = self.config.fee;
+        self.config.fee = fee;
+
+        self.new_pair.set_inner(PoolPair {
+            fee,
+            payer: self.authority.key(),
+            bump: bumps.new_pair,
+            ..self.pair.clone().into_inner()
+        });
+
+        emit!(FeeUpdated {
+            config: self.config.key(),
+            authority: self.authority.key(),
+            old_fee,
+            new_fee: fee,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -1,12 +1,16 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{burn, transfer, Burn, Mint, Token, TokenAccount, Transfer}
+    token_interface::{
+        burn, transfer_checked, Burn, Mint, TokenAccount, TokenInterface, TransferChecked,
+    },
 };
-use constant_product_curve::ConstantProduct;
 use crate::{
+    curves,
     error::AmmError,
-    state::Config,
+    events::LiquidityRemoved,
+    state::{Config, Oracle},
+    utils::amount_after_transfer_fee,
 };
 
 #[derive(Accounts)]
@@ -14,10 +18,13 @@ pub struct Withdraw<'info>{
     #[account(mut)]
     pub lp_provider: Signer<'info>,
 
-    pub mint_x: Account<'info, Mint>,
-    pub mint_y: Account<'info, Mint>,
+    #[account(mint::token_program = token_program_x)]
+    pub mint_x: InterfaceAccount<'info, Mint>,
+    #[account(mint::token_program = token_program_y)]
+    pub mint_y: InterfaceAccount<'info, Mint>,
 
     #[account(
+        mut,
         has_one = mint_x,
         has_one = mint_y,
         seeds = [b"config", config.seed.to_le_bytes().as_ref()],
@@ -25,51 +32,67 @@ pub struct Withdraw<'info>{
     )]
     pub config: Account<'info, Config>,
 
+    #[account(
+        mut,
+        has_one = config,
+        seeds = [b"oracle", config.key().as_ref()],
+        bump = oracle.bump,
+    )]
+    pub oracle: Account<'info, Oracle>,
+
     #[account(
         mut,
         seeds = [b"lp", config.key().as_ref()],
         bump = config.lp_bump,
         mint::decimals = 6,
         mint::authority = config,
+        mint::token_program = token_program,
     )]
-    pub mint_lp: Account<'info, Mint>,
+    pub mint_lp: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
         associated_token::mint = mint_x,
         associated_token::authority = config,
+        associated_token::token_program = token_program_x,
     )]
-    pub vault_x: Account<'info, TokenAccount>,
+    pub vault_x: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
         associated_token::mint = mint_y,
         associated_token::authority = config,
+        associated_token::token_program = token_program_y,
     )]
-    pub vault_y: Account<'info, TokenAccount>,
+    pub vault_y: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
         associated_token::authority = lp_provider,
-        associated_token::mint = mint_x
+        associated_token::mint = mint_x,
+        associated_token::token_program = token_program_x,
     )]
-    pub lp_provider_x: Account<'info, TokenAccount>,
+    pub lp_provider_x: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
         associated_token::authority = lp_provider,
-        associated_token::mint = mint_y
+        associated_token::mint = mint_y,
+        associated_token::token_program = token_program_y,
     )]
-    pub lp_provider_y: Account<'info, TokenAccount>,
+    pub lp_provider_y: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
         associated_token::mint = mint_lp,
-        associated_token::authority = config,
+        associated_token::authority = lp_provider,
+        associated_token::token_program = token_program,
     )]
-    pub lp_provider_lp: Account<'info, TokenAccount>,
+    pub lp_provider_lp: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub token_program_x: Interface<'info, TokenInterface>,
+    pub token_program_y: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
@@ -83,22 +106,49 @@ impl<'info> Withdraw <'info>{
         min_y: u64
     ) -> Result<()>{
 
-        require!(self.config.locked == false, AmmError::StillLocked);
+        require!(!self.config.locked, AmmError::StillLocked);
         require!(lp_amount!=0, AmmError::ZeroAmount);
         require!(min_x!=0 || min_y!=0, AmmError::IncorrectAmmount);
 
-        let amounts = ConstantProduct::xy_withdraw_amounts_from_l(
-            self.vault_x.amount,
-            self.vault_y.amount, 
+        let (reserve_x, reserve_y) = self.config.reserves(self.vault_x.amount, self.vault_y.amount)?;
+        let now = Clock::get()?.unix_timestamp;
+        self.oracle.update(&mut self.config, reserve_x, reserve_y, now);
+
+        let amounts = curves::withdraw_amounts(
+            &self.config,
+            reserve_x,
+            reserve_y, 
             self.mint_lp.supply,
-            lp_amount, 
-            6).unwrap();
+            lp_amount,
+        )?;
 
-        require!(min_x<= amounts.x && min_y<= amounts.y, AmmError::IncorrectAmmount);
+        // Slippage bounds apply to what reaches the provider after transfer fees
+        let received_x = amount_after_transfer_fee(&self.mint_x, amounts.x)?;
+        let received_y = amount_after_transfer_fee(&self.mint_y, amounts.y)?;
+        require!(min_x<= received_x && min_y<= received_y, AmmError::SlippageExceeded);
 
         self.withdraw_tokens(true, amounts.x)?;
         self.withdraw_tokens(false, amounts.y)?;
         self.burn_lp_tokens(lp_amount)?;
+
+        self.vault_x.reload()?;
+        self.vault_y.reload()?;
+        let (reserve_x_after, reserve_y_after) = self.config.reserves(self.vault_x.amount, self.vault_y.amount)?;
+
+        emit!(LiquidityRemoved {
+            config: self.config.key(),
+            user: self.lp_provider.key(),
+            amount_x: amounts.x,
+            amount_y: amounts.y,
+            fee_x: 0,
+            fee_y: 0,
+            lp_burned: lp_amount,
+            reserve_x_before: reserve_x,
+            reserve_y_before: reserve_y,
+            reserve_x_after,
+            reserve_y_after,
+            timestamp: now,
+        });
         
         Ok(())
     }
@@ -108,17 +158,16 @@ impl<'info> Withdraw <'info>{
         is_x: bool, 
         amount: u64
     ) -> Result<()> {
-
-        let cpi_program = self.token_program.to_account_info();
-
-        let (from, to) = match is_x {
-            true => (self.vault_x.to_account_info(), self.lp_provider_x.to_account_info()),
-            false => (self.vault_y.to_account_info(), self.lp_provider_y.to_account_info()),
+        let (from, to, mint, token_program) = match is_x {
+            true => (&self.vault_x, &self.lp_provider_x, &self.mint_x, &self.token_program_x),
+            false => (&self.vault_y, &self.lp_provider_y, &self.mint_y, &self.token_program_y),
         };        
 
-        let cpi_accounts = Transfer {
-            from,
-            to,
+        let cpi_program = token_program.to_account_info();
+        let cpi_accounts = TransferChecked {
+            from: from.to_account_info(),
+            to: to.to_account_info(),
+            mint: mint.to_account_info(),
             authority: self.config.to_account_info(),
         };
 
@@ -131,7 +180,7 @@ impl<'info> Withdraw <'info>{
 
         let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
 
-        transfer(cpi_ctx, amount)?;
+        transfer_checked(cpi_ctx, amount, mint.decimals)?;
         
         Ok(())
     }
@@ -0,0 +1,244 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        burn, transfer_checked, Burn, Mint, TokenAccount, TokenInterface, TransferChecked,
+    },
+};
+use crate::{
+    curves,
+    error::AmmError,
+    events::LiquidityRemoved,
+    state::{Config, Oracle, PoolStats},
+    utils::amount_after_transfer_fee,
+};
+
+#[derive(Accounts)]
+pub struct WithdrawSingle<'info>{
+    #[account(mut)]
+    pub lp_provider: Signer<'info>,
+
+    #[account(mint::token_program = token_program_x)]
+    pub mint_x: InterfaceAccount<'info, Mint>,
+    #[account(mint::token_program = token_program_y)]
+    pub mint_y: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        has_one = mint_x,
+        has_one = mint_y,
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.config_bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        has_one = config,
+        seeds = [b"oracle", config.key().as_ref()],
+        bump = oracle.bump,
+    )]
+    pub oracle: Account<'info, Oracle>,
+
+    #[account(
+        mut,
+        has_one = config,
+        seeds = [b"stats", config.key().as_ref()],
+        bump = pool_stats.bump,
+    )]
+    pub pool_stats: Account<'info, PoolStats>,
+
+    #[account(
+        mut,
+        seeds = [b"lp", config.key().as_ref()],
+        bump = config.lp_bump,
+        mint::decimals = 6,
+        mint::authority = config,
+        mint::token_program = token_program,
+    )]
+    pub mint_lp: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_x,
+        associated_token::authority = config,
+        associated_token::token_program = token_program_x,
+    )]
+    pub vault_x: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_y,
+        associated_token::authority = config,
+        associated_token::token_program = token_program_y,
+    )]
+    pub vault_y: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = lp_provider,
+        associated_token::authority = lp_provider,
+        associated_token::mint = mint_x,
+        associated_token::token_program = token_program_x,
+    )]
+    pub lp_provider_x: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = lp_provider,
+        associated_token::authority = lp_provider,
+        associated_token::mint = mint_y,
+        associated_token::token_program = token_program_y,
+    )]
+    pub lp_provider_y: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_lp,
+        associated_token::authority = lp_provider,
+        associated_token::token_program = token_program,
+    )]
+    pub lp_provider_lp: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub token_program_x: Interface<'info, TokenInterface>,
+    pub token_program_y: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> WithdrawSingle <'info>{
+
+    // Withdraws only token x (or y): the LP share of the other token is swapped back into
+    // the pool, paying the swap fee
+    pub fn withdraw_single(
+        &mut self,
+        is_x: bool,
+        lp_amount: u64,
+        min_out: u64
+    ) -> Result<()>{
+        require!(!self.config.locked, AmmError::StillLocked);
+        require!(lp_amount != 0, AmmError::ZeroAmount);
+
+        let (reserve_x, reserve_y) = self.config.reserves(self.vault_x.amount, self.vault_y.amount)?;
+        let now = Clock::get()?.unix_timestamp;
+        self.oracle.update(&mut self.config, reserve_x, reserve_y, now);
+
+        let supply = self.mint_lp.supply;
+        let amounts = curves::withdraw_amounts(&self.config, reserve_x, reserve_y, supply, lp_amount)?;
+
+        // Swap the unwanted side against what is left in the pool once the share is out
+        let (amount_kept, amount_swapped) = match is_x {
+            true => (amounts.x, amounts.y),
+            false => (amounts.y, amounts.x),
+        };
+        let (swapped_out, fee) = match amount_swapped {
+            0 => (0, 0),
+            _ => {
+                let res = curves::swap(
+                    &self.config,
+                    reserve_x - amounts.x,
+                    reserve_y - amounts.y,
+                    !is_x,
+                    amount_swapped,
+                    now,
+                )?;
+                self.config.accrue_protocol_fee(!is_x, res.fee)?;
+                self.pool_stats.record_swap(!is_x, amount_swapped, res.amount_out, res.fee);
+                (res.amount_out, res.fee)
+            },
+        };
+
+        let amount = amount_kept.checked_add(swapped_out).ok_or(AmmError::Overflow)?;
+
+        // The slippage bound applies to what reaches the provider after the transfer fee
+        let mint_out = match is_x {
+            true => &self.mint_x,
+            false => &self.mint_y,
+        };
+        let received = amount_after_transfer_fee(mint_out, amount)?;
+        require!(received != 0, AmmError::ZeroOutput);
+        require!(received >= min_out, AmmError::SlippageExceeded);
+
+        self.withdraw_tokens(is_x, amount)?;
+        self.burn_lp_tokens(lp_amount)?;
+
+        self.vault_x.reload()?;
+        self.vault_y.reload()?;
+        let (reserve_x_after, reserve_y_after) = self.config.reserves(self.vault_x.amount, self.vault_y.amount)?;
+
+        let (amount_x, amount_y, fee_x, fee_y) = match is_x {
+            true => (amount, 0, 0, fee),
+            false => (0, amount, fee, 0),
+        };
+
+        emit!(LiquidityRemoved {
+            config: self.config.key(),
+            user: self.lp_provider.key(),
+            amount_x,
+            amount_y,
+            fee_x,
+            fee_y,
+            lp_burned: lp_amount,
+            reserve_x_before: reserve_x,
+            reserve_y_before: reserve_y,
+            reserve_x_after,
+            reserve_y_after,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    pub fn withdraw_tokens(
+        &self, 
+        is_x: bool, 
+        amount: u64
+    ) -> Result<()> {
+        let (from, to, mint, token_program) = match is_x {
+            true => (&self.vault_x, &self.lp_provider_x, &self.mint_x, &self.token_program_x),
+            false => (&self.vault_y, &self.lp_provider_y, &self.mint_y, &self.token_program_y),
+        };        
+
+        let cpi_program = token_program.to_account_info();
+        let cpi_accounts = TransferChecked {
+            from: from.to_account_info(),
+            to: to.to_account_info(),
+            mint: mint.to_account_info(),
+            authority: self.config.to_account_info(),
+        };
+
+        let seeds = &[
+            &b"config"[..],
+            &self.config.seed.to_le_bytes(),
+            &[self.config.config_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+        transfer_checked(cpi_ctx, amount, mint.decimals)?;
+        
+        Ok(())
+    }
+
+    pub fn burn_lp_tokens(
+        &self, 
+        amount: u64
+    ) -> Result<()> {
+        let cpi_program = self.token_program.to_account_info();
+
+        let cpi_accounts = Burn {
+            mint: self.mint_lp.to_account_info(),
+            from: self.lp_provider_lp.to_account_info(),
+            authority: self.lp_provider.to_account_info(),
+        };
+
+        let cpi_context = CpiContext::new(cpi_program, cpi_accounts);
+
+        burn(cpi_context, amount)?;
+
+        Ok(())
+    }
+
+}
@@ -1,7 +1,10 @@
 pub mod constants;
+pub mod curves;
 pub mod error;
+pub mod events;
 pub mod instructions;
 pub mod state;
+pub mod utils;
 
 use anchor_lang::prelude::*;
 
@@ -19,9 +22,11 @@ pub mod amm {
         ctx: Context<Initialize>, 
         seed: u64, 
         fee: u16, 
-        authority: Option<Pubkey>
+        authority: Option<Pubkey>,
+        curve_type: CurveType,
+        amp: u64
     ) -> Result<()> {
-        ctx.accounts.init(seed, fee, authority, &ctx.bumps)
+        ctx.accounts.init(seed, fee, authority, curve_type, amp, &ctx.bumps)
     }
 
     pub fn deposit(
@@ -42,6 +47,24 @@ pub mod amm {
         ctx.accounts.withdraw(amount, min_x, min_y)
     }
 
+    pub fn deposit_single(
+        ctx: Context<DepositSingle>, 
+        is_x: bool, 
+        amount_in: u64, 
+        min_lp_out: u64
+    ) -> Result<()> {
+        ctx.accounts.deposit_single(is_x, amount_in, min_lp_out)
+    }
+
+    pub fn withdraw_single(
+        ctx: Context<WithdrawSingle>, 
+        is_x: bool, 
+        lp_amount: u64, 
+        min_out: u64
+    ) -> Result<()> {
+        ctx.accounts.withdraw_single(is_x, lp_amount, min_out)
+    }
+
     pub fn swap(
         ctx: Context<Swap>, 
         is_x: bool, 
@@ -50,4 +73,148 @@ pub mod amm {
     ) -> Result<()> {
         ctx.accounts.swap(is_x, amount_in, min_amount_out)
     }
+
+    pub fn swap_exact_out(
+        ctx: Context<Swap>, 
+        is_x: bool, 
+        amount_out: u64, 
+        max_amount_in: u64
+    ) -> Result<()> {
+        ctx.accounts.swap_exact_out(is_x, amount_out, max_amount_in)
+    }
+
+    pub fn swap_route<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Swap<'info>>, 
+        is_x: bool, 
+        amount_in: u64, 
+        min_amount_out: u64
+    ) -> Result<()> {
+        ctx.accounts.swap_route(is_x, amount_in, min_amount_out, ctx.program_id, ctx.remaining_accounts)
+    }
+
+    pub fn lock(ctx: Context<UpdateConfig>) -> Result<()> {
+        ctx.accounts.lock()
+    }
+
+    pub fn unlock(ctx: Context<UpdateConfig>) -> Result<()> {
+        ctx.accounts.unlock()
+    }
+
+    pub fn update_fee(ctx: Context<UpdateFee>, fee: u16) -> Result<()> {
+        ctx.accounts.update_fee(fee, &ctx.bumps)
+    }
+
+    pub fn ramp_amp(ctx: Context<UpdateConfig>, amp_target: u64, ramp_end: i64) -> Result<()> {
+        ctx.accounts.ramp_amp(amp_target, ramp_end)
+    }
+
+    pub fn transfer_authority(
+        ctx: Context<UpdateConfig>, 
+        new_authority: Option<Pubkey>
+    ) -> Result<()> {
+        ctx.accounts.transfer_authority(new_authority)
+    }
+
+    pub fn update_protocol_fee(ctx: Context<UpdateConfig>, protocol_fee: u16) -> Result<()> {
+        ctx.accounts.update_protocol_fee(protocol_fee)
+    }
+
+    pub fn collect_protocol_fees(ctx: Context<CollectProtocolFees>) -> Result<()> {
+        ctx.accounts.collect_protocol_fees()
+    }
+
+    pub fn get_twap(ctx: Context<GetTwap>, window: i64) -> Result<Twap> {
+        ctx.accounts.get_twap(window)
+    }
+
+    pub fn initialize_concentrated(
+        ctx: Context<InitializeConcentrated>, 
+        seed: u64, 
+        fee: u16, 
+        authority: Option<Pubkey>,
+        tick_spacing: u16, 
+        sqrt_price: u128
+    ) -> Result<()> {
+        ctx.accounts.init(seed, fee, authority, tick_spacing, sqrt_price, &ctx.bumps)
+    }
+
+    pub fn initialize_tick_array(ctx: Context<InitializeTickArray>, start_tick: i32) -> Result<()> {
+        ctx.accounts.init(start_tick)
+    }
+
+    pub fn open_position(
+        ctx: Context<OpenPosition>, 
+        tick_lower: i32, 
+        tick_upper: i32
+    ) -> Result<()> {
+        ctx.accounts.open_position(tick_lower, tick_upper, &ctx.bumps)
+    }
+
+    pub fn increase_liquidity(
+        ctx: Context<ModifyPosition>, 
+        liquidity: u128, 
+        max_x: u64, 
+        max_y: u64
+    ) -> Result<()> {
+        ctx.accounts.increase_liquidity(liquidity, max_x, max_y)
+    }
+
+    pub fn decrease_liquidity(
+        ctx: Context<ModifyPosition>, 
+        liquidity: u128, 
+        min_x: u64, 
+        min_y: u64
+    ) -> Result<()> {
+        ctx.accounts.decrease_liquidity(liquidity, min_x, min_y)
+    }
+
+    pub fn collect_fees(ctx: Context<ModifyPosition>) -> Result<()> {
+        ctx.accounts.collect_fees()
+    }
+
+    // The account is closed by the constraints once it holds no liquidity and no fees
+    pub fn close_position(_ctx: Context<ClosePosition>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn swap_concentrated<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SwapConcentrated<'info>>, 
+        is_x: bool, 
+        amount_in: u64, 
+        min_amount_out: u64
+    ) -> Result<()> {
+        ctx.accounts.swap_concentrated(is_x, amount_in, min_amount_out, ctx.remaining_accounts)
+    }
+
+    pub fn flash_loan(ctx: Context<FlashLoan>, is_x: bool, amount: u64) -> Result<()> {
+        ctx.accounts.flash_loan(is_x, amount)
+    }
+
+    pub fn flash_repay(ctx: Context<FlashLoan>) -> Result<()> {
+        ctx.accounts.flash_repay()
+    }
+
+    pub fn initialize_farm(ctx: Context<InitializeFarm>) -> Result<()> {
+        ctx.accounts.init(&ctx.bumps)
+    }
+
+    pub fn fund_farm(ctx: Context<FundFarm>, amount: u64, duration: u64) -> Result<()> {
+        ctx.accounts.fund_farm(amount, duration)
+    }
+
+    pub fn stake_lp(ctx: Context<StakeLp>, amount: u64) -> Result<()> {
+        ctx.accounts.stake_lp(amount, &ctx.bumps)
+    }
+
+    pub fn unstake_lp(ctx: Context<StakeLp>, amount: u64) -> Result<()> {
+        ctx.accounts.unstake_lp(amount, &ctx.bumps)
+    }
+
+    pub fn emergency_unstake_lp(ctx: Context<StakeLp>) -> Result<()> {
+        ctx.accounts.emergency_unstake_lp()
+    }
+
+    pub fn claim_rewards(ctx: Context<StakeLp>) -> Result<()> {
+        ctx.accounts.claim_rewards(&ctx.bumps)
+    }
 }
@@ -0,0 +1,153 @@
+use anchor_lang::prelude::*;
+use crate::constants::TICK_ARRAY_SIZE;
+
+#[account]
+#[derive(InitSpace)]
+pub struct ConcentratedPool{
+    pub config: Pubkey, // config of the pool, holding its mints, vaults and fees
+    pub tick_spacing: u16, // positions can only start and end on multiples of this
+    pub tick_current: i32, // greatest tick at or below the current price
+    pub sqrt_price: u128, // sqrt of the price of x in y, as Q64.64
+    pub liquidity: u128, // liquidity of the positions in range
+    pub fee_growth_global_x: u128, // LP fees in x earned per unit of liquidity, as Q64.64
+    pub fee_growth_global_y: u128, // LP fees in y earned per unit of liquidity, as Q64.64
+    pub bump: u8, // bump for the pool account
+}
+
+impl ConcentratedPool {
+
+    // Number of ticks covered by one tick array
+    pub fn tick_array_span(&self) -> i32 {
+        self.tick_spacing as i32 * TICK_ARRAY_SIZE as i32
+    }
+
+    // LP fees in x and y earned per unit of liquidity inside [tick_lower, tick_upper)
+    pub fn fee_growth_inside(&self, tick_lower: i32, lower: &Tick, tick_upper: i32, upper: &Tick) -> (u128, u128) {
+        let inside = |global: u128, lower_outside: u128, upper_outside: u128| {
+            let below = match self.tick_current >= tick_lower {
+                true => lower_outside,
+                false => global.wrapping_sub(lower_outside),
+            };
+            let above = match self.tick_current < tick_upper {
+                true => upper_outside,
+                false => global.wrapping_sub(upper_outside),
+            };
+            global.wrapping_sub(below).wrapping_sub(above)
+        };
+
+        (
+            inside(self.fee_growth_global_x, lower.fee_growth_outside_x, upper.fee_growth_outside_x),
+            inside(self.fee_growth_global_y, lower.fee_growth_outside_y, upper.fee_growth_outside_y),
+        )
+    }
+}
+
+#[account(zero_copy(unsafe))]
+#[repr(C, packed)]
+pub struct TickArray{
+    pub config: Pubkey, // pool the ticks belong to
+    pub start_tick: i32, // first tick of the array, a multiple of the array span
+    pub ticks: [Tick; TICK_ARRAY_SIZE], // ticks start_tick, start_tick + tick_spacing, ...
+}
+
+#[zero_copy(unsafe)]
+#[repr(C, packed)]
+pub struct Tick {
+    pub liquidity_net: i128, // liquidity added when the price crosses the tick going up
+    pub liquidity_gross: u128, // liquidity of all positions using the tick, zero if unused
+    pub fee_growth_outside_x: u128, // fee growth on the other side of the tick from the price
+    pub fee_growth_outside_y: u128,
+}
+
+impl TickArray {
+
+    pub fn contains(&self, tick: i32, tick_spacing: u16) -> bool {
+        let span = tick_spacing as i32 * TICK_ARRAY_SIZE as i32;
+        tick >= self.start_tick && tick < self.start_tick + span
+    }
+
+    pub fn tick(&self, tick: i32, tick_spacing: u16) -> Option<Tick> {
+        match self.contains(tick, tick_spacing) && tick % tick_spacing as i32 == 0 {
+            true => Some(self.ticks[((tick - self.start_tick) / tick_spacing as i32) as usize]),
+            false => None,
+        }
+    }
+
+    // Looks for an initialized tick from `tick` on, going down (`lte`) or up, without leaving
+    // the array. Returns it, or the last tick of the array in that direction and false.
+    // `tick` has to be a tick of the array.
+    pub fn next_initialized_tick(&self, tick: i32, tick_spacing: u16, lte: bool) -> (i32, bool) {
+        let spacing = tick_spacing as i32;
+        let start_tick = self.start_tick;
+        let mut offset = (tick - start_tick) / spacing;
+
+        loop {
+            let tick = self.ticks[offset as usize];
+            if tick.is_initialized() {
+                return (start_tick + offset * spacing, true);
+            }
+
+            match lte {
+                true if offset == 0 => return (start_tick, false),
+                false if offset == TICK_ARRAY_SIZE as i32 - 1 => return (start_tick + offset * spacing, false),
+                true => offset -= 1,
+                false => offset += 1,
+            }
+        }
+    }
+
+    pub fn tick_mut(&mut self, tick: i32, tick_spacing: u16) -> Option<&mut Tick> {
+        match self.contains(tick, tick_spacing) && tick % tick_spacing as i32 == 0 {
+            true => Some(&mut self.ticks[((tick - self.start_tick) / tick_spacing as i32) as usize]),
+            false => None,
+        }
+    }
+}
+
+impl Tick {
+
+    pub fn is_initialized(&self) -> bool {
+        self.liquidity_gross != 0
+    }
+
+    // Moves the fee growth outside to the other side of the price and returns the liquidity
+    // to add when crossing upwards
+    pub fn cross(&mut self, fee_growth_global_x: u128, fee_growth_global_y: u128) -> i128 {
+        self.fee_growth_outside_x = fee_growth_global_x.wrapping_sub(self.fee_growth_outside_x);
+        self.fee_growth_outside_y = fee_growth_global_y.wrapping_sub(self.fee_growth_outside_y);
+        self.liquidity_net
+    }
+
+    // Adds `liquidity_delta` for a position starting (`is_upper` false) or ending at the tick,
+    // returns if the tick went from unused to used or the other way around
+    pub fn update(
+        &mut self,
+        tick: i32,
+        tick_current: i32,
+        liquidity_delta: i128,
+        is_upper: bool,
+        fee_growth_global_x: u128,
+        fee_growth_global_y: u128,
+    ) -> Option<bool> {
+        let was_initialized = self.is_initialized();
+
+        self.liquidity_gross = self.liquidity_gross.checked_add_signed(liquidity_delta)?;
+        self.liquidity_net = match is_upper {
+            true => self.liquidity_net.checked_sub(liquidity_delta)?,
+            false => self.liquidity_net.checked_add(liquidity_delta)?,
+        };
+
+        // By convention all fees so far were earned below a tick the price is above of
+        if !was_initialized && tick <= tick_current {
+            self.fee_growth_outside_x = fee_growth_global_x;
+            self.fee_growth_outside_y = fee_growth_global_y;
+        }
+
+        Some(was_initialized != self.is_initialized())
+    }
+
+    pub fn clear(&mut self) {
+        self.fee_growth_outside_x = 0;
+        self.fee_growth_outside_y = 0;
+    }
+}
@@ -1,4 +1,11 @@
 use anchor_lang::prelude::*;
+use amm_math::Q64;
+use crate::{constants::MAX_PROTOCOL_FEE, error::AmmError};
+
+// Prices go into the accumulators as Q32.32 and are capped here, so a window of up to 2^32
+// seconds always fits in the u128 difference of two accumulator values
+const PRICE_SHIFT: u32 = 32;
+const PRICE_CAP: u128 = u128::MAX >> 32;
 
 #[account]
 #[derive(InitSpace)]
@@ -10,5 +17,138 @@ pub struct Config{
     pub fee: u16, // swap fee in basis points(percentage)
     pub locked: bool, // if the pool is locked
     pub config_bump: u8, // bump for the config account
-    pub lp_bump: u8 // bump for the LP token
-}
\ No newline at end of file
+    pub lp_bump: u8, // bump for the LP token
+    pub protocol_fee: u16, // share of the swap fee kept by the protocol, in basis points of the fee
+    pub protocol_fees_x: u64, // token x sitting in vault_x that belongs to the protocol
+    pub protocol_fees_y: u64, // token y sitting in vault_y that belongs to the protocol
+    pub price_x_cumulative: u128, // sum of the Q32.32 price of x in y times the seconds it held
+    pub price_y_cumulative: u128, // sum of the Q32.32 price of y in x times the seconds it held
+    pub last_update: i64, // when the price accumulators were last updated
+    pub curve_type: CurveType, // invariant the pool trades on
+    pub amp_initial: u64, // stable swap amplification coefficient at ramp_start
+    pub amp_target: u64, // stable swap amplification coefficient from ramp_end on
+    pub ramp_start: i64, // when the current A ramp started
+    pub ramp_end: i64, // when the current A ramp ends
+    pub flash_loan: Option<FlashLoanState>, // flash loan waiting for its flash_repay, if any
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct FlashLoanState {
+    pub is_x: bool, // if token x was borrowed
+    pub amount: u64, // principal sent out of the vault
+    pub fee: u64, // owed on top of the principal
+    pub vault_balance: u64, // vault balance before the loan, to be restored plus the fee
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum CurveType {
+    ConstantProduct, // x * y = k
+    StableSwap, // Curve's invariant, for pegged pairs
+    Concentrated, // liquidity in price ranges, see ConcentratedPool
+}
+
+impl Config {
+
+    // Vault balances minus what is owed to the protocol, i.e. what the LPs own
+    pub fn reserves(&self, vault_x: u64, vault_y: u64) -> Result<(u64, u64)> {
+        // The vaults are short of the borrowed amount until the loan is repaid
+        require!(self.flash_loan.is_none(), AmmError::FlashLoanActive);
+        let x = vault_x.checked_sub(self.protocol_fees_x).ok_or(AmmError::Underflow)?;
+        let y = vault_y.checked_sub(self.protocol_fees_y).ok_or(AmmError::Underflow)?;
+        Ok((x, y))
+    }
+
+    // Sets the protocol's share of the swap fee and returns the previous one
+    pub fn set_protocol_fee(&mut self, protocol_fee: u16) -> Result<u16> {
+        require!(protocol_fee <= MAX_PROTOCOL_FEE, AmmError::InvalidFeeAmount);
+
+        Ok(core::mem::replace(&mut self.protocol_fee, protocol_fee))
+    }
+
+    // Books the protocol's cut of a swap `fee` paid in token x or y and returns it
+    pub fn accrue_protocol_fee(&mut self, is_x: bool, fee: u64) -> Result<u64> {
+        let cut = (fee as u128)
+            .checked_mul(self.protocol_fee as u128)
+            .ok_or(AmmError::Overflow)?
+            / 10_000;
+
+        let owed = match is_x {
+            true => &mut self.protocol_fees_x,
+            false => &mut self.protocol_fees_y,
+        };
+        *owed = owed.checked_add(cut as u64).ok_or(AmmError::Overflow)?;
+
+        Ok(cut as u64)
+    }
+
+    // Amplification coefficient at `now`, moving linearly from amp_initial to amp_target
+    pub fn amp(&self, now: i64) -> u64 {
+        if now >= self.ramp_end {
+            return self.amp_target;
+        }
+        if now <= self.ramp_start {
+            return self.amp_initial;
+        }
+
+        let elapsed = (now - self.ramp_start) as i128;
+        let duration = (self.ramp_end - self.ramp_start) as i128;
+        let change = (self.amp_target as i128 - self.amp_initial as i128) * elapsed / duration;
+
+        (self.amp_initial as i128 + change) as u64
+    }
+
+    // Adds the prices given by the reserves, weighted by the seconds since the last update.
+    // The accumulators are meant to wrap around like Uniswap v2's.
+    pub fn update_price_cumulative(&mut self, reserve_x: u64, reserve_y: u64, now: i64) {
+        self.accumulate_prices(Self::prices(reserve_x, reserve_y), now);
+    }
+
+    // Same for a concentrated pool with the given sqrt_price
+    pub fn update_price_cumulative_concentrated(&mut self, sqrt_price: u128, now: i64) {
+        self.accumulate_prices(Some(Self::prices_concentrated(sqrt_price)), now);
+    }
+
+    // Q32.32 prices of x in y and of y in x given by the reserves, none for an empty pool
+    pub fn prices(reserve_x: u64, reserve_y: u64) -> Option<(u128, u128)> {
+        match reserve_x != 0 && reserve_y != 0 {
+            true => Some((
+                ((reserve_y as u128) << PRICE_SHIFT) / reserve_x as u128,
+                ((reserve_x as u128) << PRICE_SHIFT) / reserve_y as u128,
+            )),
+            false => None,
+        }
+    }
+
+    // Same for a concentrated pool, whose price is its Q64.64 sqrt_price squared. Prices past
+    // PRICE_CAP at the far ends of the tick range are capped.
+    pub fn prices_concentrated(sqrt_price: u128) -> (u128, u128) {
+        let square = |sqrt_price: u128| {
+            amm_math::mul_div(sqrt_price, sqrt_price, Q64 << PRICE_SHIFT, false)
+                .unwrap_or(PRICE_CAP)
+                .min(PRICE_CAP)
+        };
+        let inverse = amm_math::mul_div(Q64, Q64, sqrt_price, false).unwrap_or(u128::MAX);
+        (square(sqrt_price), square(inverse))
+    }
+
+    // The accumulators as they will be at `now` if the prices hold until then
+    pub fn price_cumulative_at(&self, prices: Option<(u128, u128)>, now: i64) -> (u128, u128) {
+        let elapsed = now - self.last_update;
+        match prices {
+            Some((price_x, price_y)) if elapsed > 0 => (
+                self.price_x_cumulative.wrapping_add(price_x.wrapping_mul(elapsed as u128)),
+                self.price_y_cumulative.wrapping_add(price_y.wrapping_mul(elapsed as u128)),
+            ),
+            _ => (self.price_x_cumulative, self.price_y_cumulative),
+        }
+    }
+
+    fn accumulate_prices(&mut self, prices: Option<(u128, u128)>, now: i64) {
+        if now <= self.last_update {
+            return;
+        }
+
+        (self.price_x_cumulative, self.price_y_cumulative) = self.price_cumulative_at(prices, now);
+        self.last_update = now;
+    }
+}
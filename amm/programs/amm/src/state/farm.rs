@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use crate::{
+    curves::concentrated::{mul_div, Q64},
+    error::AmmError,
+};
+
+#[account]
+#[derive(InitSpace)]
+pub struct Farm{
+    pub config: Pubkey, // pool whose LP tokens are staked
+    pub reward_mint: Pubkey, // token paid out to the stakers
+    pub reward_rate: u64, // reward tokens paid out per second, shared by all stakers
+    pub reward_per_share: u128, // rewards earned per staked LP token since the start, as Q64.64
+    pub last_update: i64, // when reward_per_share was last brought up to date
+    pub end_time: i64, // rewards stop accruing at this time
+    pub total_staked: u64, // LP tokens in the farm's LP vault
+    pub undistributed: u64, // rewards nobody earned, paid out again by the next fund_farm
+    pub bump: u8, // bump for the farm account
+}
+
+impl Farm {
+
+    // Accrues the rewards since the last update to the LP tokens staked during that time.
+    // Seconds with nothing staked set their rewards aside in `undistributed`. Never fails,
+    // so unstaking can't get stuck behind the reward math. Like the pools' fee growth,
+    // reward_per_share is allowed to wrap, only differences of it are ever used.
+    pub fn update(&mut self, now: i64) {
+        let until = now.min(self.end_time);
+        if until <= self.last_update {
+            return;
+        }
+
+        let rewards = self.reward_rate as u128 * (until - self.last_update) as u128;
+        match self.total_staked {
+            0 => self.set_aside(rewards),
+            total_staked => match mul_div(rewards, Q64, total_staked as u128, false) {
+                Ok(growth) => self.reward_per_share = self.reward_per_share.wrapping_add(growth),
+                Err(_) => self.set_aside(rewards),
+            },
+        }
+
+        self.last_update = until;
+    }
+
+    // Rewards still to be paid out by the current schedule, plus the ones nobody earned
+    pub fn remaining_rewards(&self, now: i64) -> u64 {
+        let scheduled = match now < self.end_time {
+            true => self.reward_rate.saturating_mul((self.end_time - now) as u64),
+            false => 0,
+        };
+        scheduled.saturating_add(self.undistributed)
+    }
+
+    // Keeps rewards in the vault for the next schedule instead of paying them out
+    pub fn set_aside(&mut self, rewards: u128) {
+        let rewards = u64::try_from(rewards).unwrap_or(u64::MAX);
+        self.undistributed = self.undistributed.saturating_add(rewards);
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct FarmStake{
+    pub owner: Pubkey, // only key allowed to unstake and claim
+    pub farm: Pubkey, // farm the LP tokens are staked in
+    pub amount: u64, // LP tokens staked
+    pub reward_per_share_paid: u128, // farm.reward_per_share when rewards were last booked
+    pub rewards_owed: u64, // rewards waiting to be claimed
+    pub bump: u8, // bump for the stake account
+}
+
+impl FarmStake {
+
+    // Books the rewards earned since the last update, `farm` has to be up to date
+    pub fn update(&mut self, reward_per_share: u128) -> Result<()> {
+        let growth = reward_per_share.wrapping_sub(self.reward_per_share_paid);
+        let earned = mul_div(self.amount as u128, growth, Q64, false)?;
+
+        let earned = u64::try_from(earned).map_err(|_| AmmError::Overflow)?;
+        self.rewards_owed = self.rewards_owed.checked_add(earned).ok_or(AmmError::Overflow)?;
+        self.reward_per_share_paid = reward_per_share;
+
+        Ok(())
+    }
+}
@@ -1,3 +1,15 @@
 pub mod config;
+pub mod oracle;
+pub mod pool_stats;
+pub mod concentrated_pool;
+pub mod position;
+pub mod pool_registry;
+pub mod farm;
 
-pub use config::*;
\ No newline at end of file
+pub use config::*;
+pub use oracle::*;
+pub use pool_stats::*;
+pub use concentrated_pool::*;
+pub use position::*;
+pub use pool_registry::*;
+pub use farm::*;
@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::{ORACLE_INTERVAL, ORACLE_OBSERVATIONS},
+    error::AmmError,
+    state::Config,
+};
+
+#[account]
+#[derive(InitSpace)]
+pub struct Oracle{
+    pub config: Pubkey, // pool the observations belong to
+    pub index: u16, // slot of the latest observation
+    pub bump: u8, // bump for the oracle account
+    #[max_len(ORACLE_OBSERVATIONS)]
+    pub observations: Vec<Observation>, // ring buffer of past accumulator values
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct Observation {
+    pub timestamp: i64, // when the accumulators were sampled
+    pub price_x_cumulative: u128, // Config::price_x_cumulative at that time
+    pub price_y_cumulative: u128, // Config::price_y_cumulative at that time
+}
+
+// Time-weighted average prices as Q32.32 fixed point numbers
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct Twap {
+    pub price_x: u128, // price of token x in token y
+    pub price_y: u128, // price of token y in token x
+    pub start: i64, // timestamp of the older observation
+    pub end: i64, // when the average was taken
+}
+
+impl Oracle {
+
+    pub fn new(config: Pubkey, bump: u8, now: i64) -> Self {
+        Self {
+            config,
+            index: 0,
+            bump,
+            observations: vec![Observation {
+                timestamp: now,
+                price_x_cumulative: 0,
+                price_y_cumulative: 0,
+            }],
+        }
+    }
+
+    // Brings the pool accumulators up to `now` with the reserves held until then and stores a
+    // new observation if the last one is old enough. Has to run before the reserves change.
+    pub fn update(&mut self, config: &mut Config, reserve_x: u64, reserve_y: u64, now: i64) {
+        config.update_price_cumulative(reserve_x, reserve_y, now);
+        self.observe(config, now);
+    }
+
+    // Same for a concentrated pool, with the sqrt_price it held until `now`
+    pub fn update_concentrated(&mut self, config: &mut Config, sqrt_price: u128, now: i64) {
+        config.update_price_cumulative_concentrated(sqrt_price, now);
+        self.observe(config, now);
+    }
+
+    fn observe(&mut self, config: &Config, now: i64) {
+        if now - self.latest().timestamp < ORACLE_INTERVAL {
+            return;
+        }
+
+        let observation = Observation {
+            timestamp: now,
+            price_x_cumulative: config.price_x_cumulative,
+            price_y_cumulative: config.price_y_cumulative,
+        };
+
+        if self.observations.len() < ORACLE_OBSERVATIONS {
+            self.observations.push(observation);
+            self.index = (self.observations.len() - 1) as u16;
+        } else {
+            self.index = (self.index + 1) % ORACLE_OBSERVATIONS as u16;
+            self.observations[self.index as usize] = observation;
+        }
+    }
+
+    pub fn latest(&self) -> &Observation {
+        &self.observations[self.index as usize]
+    }
+
+    // Average prices from the newest observation at least `window` seconds old until `now`.
+    // `price_cumulative` are the pool accumulators brought up to `now` with the live price,
+    // see Config::price_cumulative_at, so the time since the last observation counts too.
+    pub fn twap(&self, window: i64, now: i64, price_cumulative: (u128, u128)) -> Result<Twap> {
+        require!(window > 0, AmmError::InvalidTwapWindow);
+
+        let start = self.observations
+            .iter()
+            .filter(|o| o.timestamp <= now - window)
+            .max_by_key(|o| o.timestamp)
+            .ok_or(AmmError::OracleNotReady)?;
+
+        // The accumulators wrap around, only their differences are meaningful
+        let elapsed = (now - start.timestamp) as u128;
+        Ok(Twap {
+            price_x: price_cumulative.0.wrapping_sub(start.price_x_cumulative) / elapsed,
+            price_y: price_cumulative.1.wrapping_sub(start.price_y_cumulative) / elapsed,
+            start: start.timestamp,
+            end: now,
+        })
+    }
+}
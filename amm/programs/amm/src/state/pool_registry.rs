@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct PoolRegistry{
+    pub pool_count: u64, // pools created so far, the next one gets this index
+    pub bump: u8, // bump for the registry account
+}
+
+// Canonical pool of a mint pair and fee tier, found at
+// [b"pair", mint_x, mint_y, fee] with mint_x sorted before mint_y
+#[account]
+#[derive(InitSpace)]
+pub struct PoolPair{
+    pub config: Pubkey, // the pool
+    pub mint_x: Pubkey, // lower of the two mints
+    pub mint_y: Pubkey, // higher of the two mints
+    pub fee: u16, // fee tier the pool was created with
+    pub index: u64, // position of the pool in the registry
+    pub payer: Pubkey, // paid the rent, gets it back when the pair account moves
+    pub bump: u8, // bump for the pair account
+}
+
+// Pool at a position in the registry, found at [b"pool_index", index] so the pools can be
+// listed by walking the indices up to PoolRegistry::pool_count
+#[account]
+#[derive(InitSpace)]
+pub struct PoolIndex{
+    pub config: Pubkey, // the pool
+    pub bump: u8, // bump for the index account
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct PoolStats{
+    pub config: Pubkey, // pool the stats belong to
+    pub volume_x: u128, // token x swapped into or out of the pool
+    pub volume_y: u128, // token y swapped into or out of the pool
+    pub fees_x: u128, // swap fees paid in token x, LP and protocol shares together
+    pub fees_y: u128, // swap fees paid in token y, LP and protocol shares together
+    pub swap_count: u64, // number of swaps, implied ones of single-sided withdrawals included
+    pub bump: u8, // bump for the stats account
+}
+
+impl PoolStats {
+
+    // Books a swap of `amount_in` token x (or y) for `amount_out` of the other token
+    pub fn record_swap(&mut self, is_x: bool, amount_in: u64, amount_out: u64, fee: u64) {
+        let (volume_in, volume_out) = match is_x {
+            true => (&mut self.volume_x, &mut self.volume_y),
+            false => (&mut self.volume_y, &mut self.volume_x),
+        };
+        *volume_in = volume_in.saturating_add(amount_in as u128);
+        *volume_out = volume_out.saturating_add(amount_out as u128);
+
+        self.swap_count = self.swap_count.saturating_add(1);
+        self.record_fee(is_x, fee);
+    }
+
+    pub fn record_fee(&mut self, is_x: bool, fee: u64) {
+        let fees = match is_x {
+            true => &mut self.fees_x,
+            false => &mut self.fees_y,
+        };
+        *fees = fees.saturating_add(fee as u128);
+    }
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::{
+    curves::concentrated::{mul_div, Q64},
+    error::AmmError,
+};
+
+#[account]
+#[derive(InitSpace)]
+pub struct Position{
+    pub owner: Pubkey, // only key allowed to change the position and collect its fees
+    pub config: Pubkey, // pool the position provides liquidity to
+    pub tick_lower: i32, // the position is active from this tick
+    pub tick_upper: i32, // up to, but not including, this tick
+    pub liquidity: u128, // liquidity provided in the range
+    pub fee_growth_inside_x_last: u128, // fee growth inside the range when fees were last booked
+    pub fee_growth_inside_y_last: u128,
+    pub fees_owed_x: u64, // fees in x waiting to be collected
+    pub fees_owed_y: u64, // fees in y waiting to be collected
+    pub bump: u8, // bump for the position account
+}
+
+impl Position {
+
+    // Books the fees earned since the last update and applies `liquidity_delta`
+    pub fn update(&mut self, liquidity_delta: i128, fee_growth_inside_x: u128, fee_growth_inside_y: u128) -> Result<()> {
+        let earned = |growth: u128, last: u128| -> Result<u64> {
+            let fees = mul_div(self.liquidity, growth.wrapping_sub(last), Q64, false)?;
+            Ok(u64::try_from(fees).map_err(|_| AmmError::Overflow)?)
+        };
+
+        let fees_x = earned(fee_growth_inside_x, self.fee_growth_inside_x_last)?;
+        let fees_y = earned(fee_growth_inside_y, self.fee_growth_inside_y_last)?;
+        self.fees_owed_x = self.fees_owed_x.checked_add(fees_x).ok_or(AmmError::Overflow)?;
+        self.fees_owed_y = self.fees_owed_y.checked_add(fees_y).ok_or(AmmError::Overflow)?;
+
+        self.fee_growth_inside_x_last = fee_growth_inside_x;
+        self.fee_growth_inside_y_last = fee_growth_inside_y;
+
+        self.liquidity = self.liquidity
+            .checked_add_signed(liquidity_delta)
+            .ok_or(AmmError::InsufficientLiquidity)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+// Reward accounting of the LP farms: nothing a staker does can get stuck behind it, and
+// rewards nobody earned come back with the next schedule.
+
+use amm::{Farm, FarmStake};
+use anchor_lang::prelude::Pubkey;
+
+fn farm(reward_rate: u64, end_time: i64, total_staked: u64) -> Farm {
+    Farm {
+        config: Pubkey::default(),
+        reward_mint: Pubkey::default(),
+        reward_rate,
+        reward_per_share: 0,
+        last_update: 0,
+        end_time,
+        total_staked,
+        undistributed: 0,
+        bump: 0,
+    }
+}
+
+fn stake(amount: u64, reward_per_share_paid: u128) -> FarmStake {
+    FarmStake {
+        owner: Pubkey::default(),
+        farm: Pubkey::default(),
+        amount,
+        reward_per_share_paid,
+        rewards_owed: 0,
+        bump: 0,
+    }
+}
+
+#[test]
+fn rewards_with_nothing_staked_are_set_aside() {
+    let mut farm = farm(10, 100, 0);
+
+    farm.update(40);
+    assert_eq!(farm.reward_per_share, 0);
+    assert_eq!(farm.undistributed, 400);
+    assert_eq!(farm.remaining_rewards(40), 600 + 400);
+
+    farm.total_staked = 1;
+    farm.update(100);
+    assert_eq!(farm.undistributed, 400);
+    assert_eq!(farm.remaining_rewards(100), 400);
+}
+
+#[test]
+fn reward_per_share_wraps_around() {
+    let mut farm = farm(1, 100, 1);
+    farm.reward_per_share = u128::MAX - (1 << 64) + 1;
+    let mut stake = stake(1, farm.reward_per_share);
+
+    farm.update(3);
+    assert!(farm.reward_per_share < stake.reward_per_share_paid);
+
+    stake.update(farm.reward_per_share).unwrap();
+    assert_eq!(stake.rewards_owed, 3);
+}
+
+#[test]
+fn update_never_fails() {
+    let mut farm = farm(u64::MAX, i64::MAX, 1);
+
+    farm.update(i64::MAX);
+    assert_eq!(farm.last_update, i64::MAX);
+    assert_eq!(farm.undistributed, u64::MAX);
+}
@@ -0,0 +1,73 @@
+// The TWAP covers the whole window up to now and the accumulators hold long windows at
+// extreme prices without wrapping more than once.
+
+use amm::{Config, CurveType, Oracle};
+use anchor_lang::prelude::Pubkey;
+
+const ONE: u128 = 1 << 32; // 1.0 as Q32.32
+
+fn config() -> Config {
+    Config {
+        seed: 0,
+        authority: None,
+        mint_x: Pubkey::default(),
+        mint_y: Pubkey::default(),
+        fee: 30,
+        locked: false,
+        config_bump: 0,
+        lp_bump: 0,
+        protocol_fee: 0,
+        protocol_fees_x: 0,
+        protocol_fees_y: 0,
+        price_x_cumulative: 0,
+        price_y_cumulative: 0,
+        last_update: 0,
+        curve_type: CurveType::ConstantProduct,
+        amp_initial: 0,
+        amp_target: 0,
+        ramp_start: 0,
+        ramp_end: 0,
+        flash_loan: None,
+    }
+}
+
+#[test]
+fn twap_extends_to_now_with_the_live_price() {
+    let mut config = config();
+    let mut oracle = Oracle::new(Pubkey::default(), 0, 0);
+
+    // 1:1 for 100 seconds, then the reserves move to 1:4 and nothing trades for 300 seconds
+    oracle.update(&mut config, 1_000, 1_000, 100);
+    let live = Config::prices(1_000, 4_000);
+    let twap = oracle.twap(400, 400, config.price_cumulative_at(live, 400)).unwrap();
+
+    assert_eq!((twap.start, twap.end), (0, 400));
+    assert_eq!(twap.price_x, (ONE * 100 + 4 * ONE * 300) / 400);
+    assert_eq!(twap.price_y, (ONE * 100 + ONE / 4 * 300) / 400);
+}
+
+#[test]
+fn extreme_prices_survive_a_long_window() {
+    let mut config = config();
+    let mut oracle = Oracle::new(Pubkey::default(), 0, 0);
+    let year = 365 * 24 * 60 * 60;
+
+    // Start right before the accumulators wrap
+    config.price_x_cumulative = u128::MAX - 5;
+    config.price_y_cumulative = u128::MAX - 5;
+    oracle.observations[0].price_x_cumulative = u128::MAX - 5;
+    oracle.observations[0].price_y_cumulative = u128::MAX - 5;
+    oracle.update(&mut config, 1, u64::MAX, year);
+
+    let twap = oracle.twap(year, year, (config.price_x_cumulative, config.price_y_cumulative)).unwrap();
+    assert_eq!(twap.price_x, (u64::MAX as u128) << 32);
+    assert_eq!(twap.price_y, 0);
+}
+
+#[test]
+fn concentrated_prices_are_inverse() {
+    let (price_x, price_y) = Config::prices_concentrated(2 << 64);
+
+    assert_eq!(price_x, 4 * ONE);
+    assert_eq!(price_y, ONE / 4);
+}
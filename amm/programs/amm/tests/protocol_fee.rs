@@ -0,0 +1,50 @@
+// The protocol fee is a share of the swap fee, LPs always keep at least the other half.
+
+use amm::{Config, CurveType, MAX_FEE, MAX_PROTOCOL_FEE};
+use anchor_lang::prelude::Pubkey;
+
+fn config() -> Config {
+    Config {
+        seed: 0,
+        authority: None,
+        mint_x: Pubkey::default(),
+        mint_y: Pubkey::default(),
+        fee: 30,
+        locked: false,
+        config_bump: 0,
+        lp_bump: 0,
+        protocol_fee: 0,
+        protocol_fees_x: 0,
+        protocol_fees_y: 0,
+        price_x_cumulative: 0,
+        price_y_cumulative: 0,
+        last_update: 0,
+        curve_type: CurveType::ConstantProduct,
+        amp_initial: 0,
+        amp_target: 0,
+        ramp_start: 0,
+        ramp_end: 0,
+        flash_loan: None,
+    }
+}
+
+#[test]
+fn protocol_fee_is_capped() {
+    let mut config = config();
+
+    assert_eq!(config.set_protocol_fee(MAX_PROTOCOL_FEE).unwrap(), 0);
+    assert!(config.set_protocol_fee(MAX_PROTOCOL_FEE + 1).is_err());
+    assert!(config.set_protocol_fee(MAX_FEE).is_err());
+    assert_eq!(config.protocol_fee, MAX_PROTOCOL_FEE);
+}
+
+#[test]
+fn lps_keep_at_least_half_of_the_swap_fee() {
+    let mut config = config();
+    config.set_protocol_fee(MAX_PROTOCOL_FEE).unwrap();
+
+    for fee in [1, 2, 3, 999, 1_000_001] {
+        let cut = config.accrue_protocol_fee(true, fee).unwrap();
+        assert!(cut <= fee - cut);
+    }
+}
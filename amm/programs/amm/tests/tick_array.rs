@@ -0,0 +1,44 @@
+// Scanning a tick array for the next initialized tick stops at its own edges.
+
+use amm::{Tick, TickArray, TICK_ARRAY_SIZE};
+use anchor_lang::prelude::Pubkey;
+
+const SPACING: u16 = 10;
+const START: i32 = -320;
+const LAST: i32 = START + (TICK_ARRAY_SIZE as i32 - 1) * SPACING as i32;
+
+fn tick_array(initialized: &[i32]) -> TickArray {
+    let mut tick_array = TickArray {
+        config: Pubkey::default(),
+        start_tick: START,
+        ticks: [Tick {
+            liquidity_net: 0,
+            liquidity_gross: 0,
+            fee_growth_outside_x: 0,
+            fee_growth_outside_y: 0,
+        }; TICK_ARRAY_SIZE],
+    };
+    for tick in initialized {
+        tick_array.tick_mut(*tick, SPACING).unwrap().liquidity_gross = 1;
+    }
+    tick_array
+}
+
+#[test]
+fn finds_the_closest_initialized_tick() {
+    let tick_array = tick_array(&[-100, 50]);
+
+    assert_eq!(tick_array.next_initialized_tick(0, SPACING, true), (-100, true));
+    assert_eq!(tick_array.next_initialized_tick(0, SPACING, false), (50, true));
+    assert_eq!(tick_array.next_initialized_tick(50, SPACING, true), (50, true));
+}
+
+#[test]
+fn stops_at_the_edge_of_the_array() {
+    let tick_array = tick_array(&[]);
+
+    assert_eq!(tick_array.next_initialized_tick(0, SPACING, true), (START, false));
+    assert_eq!(tick_array.next_initialized_tick(0, SPACING, false), (LAST, false));
+    assert_eq!(tick_array.next_initialized_tick(START, SPACING, true), (START, false));
+    assert_eq!(tick_array.next_initialized_tick(LAST, SPACING, false), (LAST, false));
+}
@@ -0,0 +1,7 @@
+use anchor_lang::prelude::*;
+
+#[constant]
+pub const MAX_OWNERS: u8 = 10; // most owners a multisig can have
+
+#[constant]
+pub const MIN_RECOVERY_TIMELOCK: i64 = 2 * 24 * 60 * 60; // owners get at least two days to challenge a recovery
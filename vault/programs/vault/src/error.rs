@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum VaultError {
+    #[msg("Requested amount exceeds the unlocked balance")]
+    FundsLocked,
+    #[msg("Invalid vesting schedule")]
+    InvalidVestingSchedule,
+    #[msg("Overflow")]
+    Overflow,
+    #[msg("Signer is not an owner of the multisig")]
+    NotAnOwner,
+    #[msg("Too many owners")]
+    TooManyOwners,
+    #[msg("Duplicate owner")]
+    DuplicateOwner,
+    #[msg("Threshold must be between 1 and the number of owners")]
+    InvalidThreshold,
+    #[msg("Owner already approved this proposal")]
+    AlreadyApproved,
+    #[msg("Not enough approvals")]
+    NotEnoughApprovals,
+    #[msg("Insufficient funds in the vault")]
+    InsufficientFunds,
+    #[msg("Amount must be greater than zero")]
+    ZeroAmount,
+    #[msg("Withdrawal would leave the vault below rent exemption")]
+    RentExemptViolation,
+    #[msg("Invalid allowance")]
+    InvalidAllowance,
+    #[msg("Allowance expired")]
+    AllowanceExpired,
+    #[msg("Allowance exceeded for the current period")]
+    AllowanceExceeded,
+    #[msg("Invalid stream")]
+    InvalidStream,
+    #[msg("Nothing to claim")]
+    NothingToClaim,
+    #[msg("Signer is not the vault owner")]
+    Unauthorized,
+    #[msg("Signer is not the vault guardian")]
+    NotGuardian,
+    #[msg("Vault is frozen")]
+    VaultFrozen,
+    #[msg("Recovery timelock is too short")]
+    RecoveryTimelockTooShort,
+    #[msg("No recovery in progress")]
+    NoPendingRecovery,
+    #[msg("Recovery timelock not yet passed")]
+    RecoveryTimelockNotPassed,
+    #[msg("Revoke the vault's allowances and cancel its streams first")]
+    OpenDelegations,
+    #[msg("No guardian change in progress")]
+    NoPendingGuardianChange,
+    #[msg("Guardian change timelock not yet passed")]
+    GuardianChangeTimelockNotPassed,
+}
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct VaultDeposited {
+    pub vault_state: Pubkey,
+    pub depositor: Pubkey,
+    pub amount: u64,
+    pub balance: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VaultWithdrawn {
+    pub vault_state: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub balance: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VaultClosed {
+    pub vault_state: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VaultReopened {
+    pub vault_state: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GuardianSet {
+    pub vault_state: Pubkey,
+    pub guardian: Option<Pubkey>,
+    pub recovery_timelock: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GuardianChangeStarted {
+    pub vault_state: Pubkey,
+    pub guardian: Option<Pubkey>,
+    pub recovery_timelock: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VaultFreezeChanged {
+    pub vault_state: Pubkey,
+    pub guardian: Pubkey,
+    pub frozen: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RecoveryStarted {
+    pub vault_state: Pubkey,
+    pub guardian: Pubkey,
+    pub pending_owner: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RecoveryCancelled {
+    pub vault_state: Pubkey,
+    pub owner: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OwnerRotated {
+    pub vault_state: Pubkey,
+    pub old_owner: Pubkey,
+    pub new_owner: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::{
+    error::VaultError,
+    state::{MultisigState, WithdrawalProposal},
+};
+
+#[derive(Accounts)]
+pub struct ApproveWithdrawal<'info> {
+
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [b"multisig", multisig_state.creator.as_ref(), multisig_state.seed.to_le_bytes().as_ref()],
+        bump = multisig_state.state_bump,
+        constraint = multisig_state.is_owner(&owner.key()) @ VaultError::NotAnOwner
+    )]
+    pub multisig_state: Account<'info, MultisigState>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", multisig_state.key().as_ref(), proposal.index.to_le_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, WithdrawalProposal>,
+}
+
+impl<'info> ApproveWithdrawal <'info> {
+
+    pub fn approve_withdrawal(&mut self) -> Result<()>{
+        let owner = self.owner.key();
+        require!(!self.proposal.approvals.contains(&owner), VaultError::AlreadyApproved);
+
+        self.proposal.approvals.push(owner);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+use crate::{
+    error::VaultError,
+    events::VaultWithdrawn,
+    state::{Stream, VaultState},
+    utils::available_to_withdraw,
+};
+
+#[derive(Accounts)]
+pub struct CancelStream<'info> {
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(mut)]
+    pub recipient: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state", vault_state.creator.as_ref()],
+        bump = vault_state.state_bump,
+        constraint = vault_state.owner == signer.key() @ VaultError::Unauthorized
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        close = signer,
+        has_one = vault_state,
+        has_one = recipient,
+        seeds = [b"stream", vault_state.key().as_ref(), recipient.key().as_ref(), stream.seed.to_le_bytes().as_ref()],
+        bump = stream.bump
+    )]
+    pub stream: Account<'info, Stream>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault_state.key().as_ref()],
+        bump = vault_state.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CancelStream <'info> {
+
+    // Settles what the recipient earned up to now as far as the vault can pay it, the rest
+    // stays in the vault. Never fails on a locked, frozen or underfunded vault, so a stream
+    // can always be closed and the vault with it.
+    pub fn cancel_stream(&mut self) -> Result<()>{
+        self.vault_state.open_delegations = self.vault_state.open_delegations.saturating_sub(1);
+
+        let now = Clock::get()?.unix_timestamp;
+        let available = available_to_withdraw(&self.vault_state, &self.vault, now)?;
+        let amount = self.stream.settle(now, available);
+        if amount == 0 {
+            return Ok(());
+        }
+
+        self.vault_state.record_withdrawal(amount, self.vault.lamports(), now)?;
+
+        let system_program= self.system_program.to_account_info();
+        let accounts = Transfer {
+            from: self.vault.to_account_info(),
+            to: self.recipient.to_account_info(),
+        };
+
+        let vault_state_key = self.vault_state.key();
+        let seeds = &[
+            b"vault",
+            vault_state_key.as_ref(),
+            &[self.vault_state.vault_bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_context = CpiContext::new_with_signer(system_program, accounts, signer_seeds);
+
+        transfer(cpi_context, amount)?;
+
+        emit!(VaultWithdrawn {
+            vault_state: self.vault_state.key(),
+            recipient: self.recipient.key(),
+            amount,
+            balance: self.vault.lamports(),
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+use crate::{
+    error::VaultError,
+    events::VaultWithdrawn,
+    state::{Stream, VaultState},
+    utils::available_to_withdraw,
+};
+
+#[derive(Accounts)]
+pub struct ClaimStream<'info> {
+
+    #[account(mut)]
+    pub recipient: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state", vault_state.creator.as_ref()],
+        bump = vault_state.state_bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        has_one = vault_state,
+        has_one = recipient,
+        seeds = [b"stream", vault_state.key().as_ref(), recipient.key().as_ref(), stream.seed.to_le_bytes().as_ref()],
+        bump = stream.bump
+    )]
+    pub stream: Account<'info, Stream>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault_state.key().as_ref()],
+        bump = vault_state.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimStream <'info> {
+
+    // Pays what the vault can release right now, a stream never draws on locked funds
+    pub fn claim_stream(&mut self) -> Result<()>{
+        let now = Clock::get()?.unix_timestamp;
+        let available = available_to_withdraw(&self.vault_state, &self.vault, now)?;
+        let amount = self.stream.settle(now, available);
+        require!(amount > 0, VaultError::NothingToClaim);
+
+        self.vault_state.record_withdrawal(amount, self.vault.lamports(), now)?;
+
+        let system_program= self.system_program.to_account_info();
+        let accounts = Transfer {
+            from: self.vault.to_account_info(),
+            to: self.recipient.to_account_info(),
+        };
+
+        let vault_state_key = self.vault_state.key();
+        let seeds = &[
+            b"vault",
+            vault_state_key.as_ref(),
+            &[self.vault_state.vault_bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_context = CpiContext::new_with_signer(system_program, accounts, signer_seeds);
+
+        transfer(cpi_context, amount)?;
+
+        emit!(VaultWithdrawn {
+            vault_state: self.vault_state.key(),
+            recipient: self.recipient.key(),
+            amount,
+            balance: self.vault.lamports(),
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+use crate::{
+    error::VaultError,
+    events::VaultClosed,
+    state::VaultState,
+};
+
+#[derive(Accounts)]
+pub struct Close <'info> {
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        close = signer,
+        seeds = [b"state", vault_state.creator.as_ref()],
+        bump = vault_state.state_bump,
+        constraint = vault_state.owner == signer.key() @ VaultError::Unauthorized
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        seeds = [b"vault",vault_state.key().as_ref()],
+        bump = vault_state.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Close <'info> {
+
+    pub fn close(&mut self) -> Result<()>{
+        require!(!self.vault_state.frozen, VaultError::VaultFrozen);
+        // A reopened vault gets the same address, so allowances and streams left behind would
+        // still pass `has_one = vault_state` against it
+        require!(self.vault_state.open_delegations == 0, VaultError::OpenDelegations);
+
+        let balance = self.vault.lamports();
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            self.vault_state.unlocked_amount(balance, now) == balance,
+            VaultError::FundsLocked
+        );
+
+        let system_program= self.system_program.to_account_info();
+        let accounts = Transfer {
+            from: self.vault.to_account_info(),
+            to: self.signer.to_account_info(),
+        };
+
+        let vault_bump = self.vault_state.vault_bump;
+        let vault_state_key = self.vault_state.to_account_info().key;
+        let seeds = &[
+            b"vault", 
+            vault_state_key.as_ref(), 
+            &[vault_bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_context = CpiContext::new_with_signer(system_program, accounts, signer_seeds);
+
+        transfer(cpi_context, balance)?;
+
+        emit!(VaultClosed {
+            vault_state: self.vault_state.key(),
+            owner: self.signer.key(),
+            amount: balance,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
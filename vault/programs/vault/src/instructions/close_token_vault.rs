@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+use crate::state::TokenVaultState;
+
+#[derive(Accounts)]
+pub struct CloseTokenVault<'info> {
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mint::token_program = token_program,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = mint,
+        associated_token::authority = signer,
+        associated_token::token_program = token_program,
+    )]
+    pub signer_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        close = signer,
+        has_one = mint,
+        seeds = [b"state", signer.key().as_ref(), mint.key().as_ref()],
+        bump = token_vault_state.state_bump
+    )]
+    pub token_vault_state: Account<'info, TokenVaultState>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = token_vault_state,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CloseTokenVault <'info> {
+
+    pub fn close_token_vault(&mut self) -> Result<()>{
+        let signer_key = self.signer.key();
+        let mint_key = self.mint.key();
+        let seeds = &[
+            b"state",
+            signer_key.as_ref(),
+            mint_key.as_ref(),
+            &[self.token_vault_state.state_bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_program = self.token_program.to_account_info();
+        let cpi_accounts = TransferChecked {
+            from: self.vault.to_account_info(),
+            to: self.signer_ata.to_account_info(),
+            mint: self.mint.to_account_info(),
+            authority: self.token_vault_state.to_account_info(),
+        };
+
+        let cpi_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+        transfer_checked(cpi_context, self.vault.amount, self.mint.decimals)?;
+
+        let cpi_program = self.token_program.to_account_info();
+        let cpi_accounts = CloseAccount {
+            account: self.vault.to_account_info(),
+            destination: self.signer.to_account_info(),
+            authority: self.token_vault_state.to_account_info(),
+        };
+
+        let cpi_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+        close_account(cpi_context)?;
+        Ok(())
+    }
+}
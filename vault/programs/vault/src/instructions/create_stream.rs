@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use crate::{
+    error::VaultError,
+    state::{Stream, VaultState},
+};
+
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct CreateStream<'info> {
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub recipient: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state", vault_state.creator.as_ref()],
+        bump = vault_state.state_bump,
+        constraint = vault_state.owner == signer.key() @ VaultError::Unauthorized
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + Stream::INIT_SPACE,
+        seeds = [b"stream", vault_state.key().as_ref(), recipient.key().as_ref(), seed.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub stream: Account<'info, Stream>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateStream <'info> {
+
+    pub fn create_stream(
+        &mut self,
+        seed: u64,
+        rate: u64,
+        start: i64,
+        end: i64,
+        bumps: CreateStreamBumps
+    ) -> Result<()>{
+        require!(rate > 0 && start < end, VaultError::InvalidStream);
+
+        self.vault_state.open_delegations = self.vault_state.open_delegations
+            .checked_add(1)
+            .ok_or(VaultError::Overflow)?;
+
+        self.stream.set_inner(Stream {
+            vault_state: self.vault_state.key(),
+            recipient: self.recipient.key(),
+            seed,
+            rate,
+            start,
+            end,
+            last_claimed: start,
+            bump: bumps.stream,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+use crate::{
+    error::VaultError,
+    events::VaultWithdrawn,
+    state::{Allowance, VaultState},
+    utils::check_vault_withdrawal,
+};
+
+#[derive(Accounts)]
+pub struct DelegatedWithdraw<'info> {
+
+    #[account(mut)]
+    pub delegate: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state", vault_state.creator.as_ref()],
+        bump = vault_state.state_bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        has_one = vault_state,
+        has_one = delegate,
+        seeds = [b"allowance", vault_state.key().as_ref(), delegate.key().as_ref()],
+        bump = allowance.bump
+    )]
+    pub allowance: Account<'info, Allowance>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault_state.key().as_ref()],
+        bump = vault_state.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> DelegatedWithdraw <'info> {
+
+    pub fn delegated_withdraw(&mut self, amount: u64) -> Result<()>{
+        check_vault_withdrawal(&self.vault, amount)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(now < self.allowance.expiry, VaultError::AllowanceExpired);
+
+        self.allowance.refresh_period(now);
+        let spent = self.allowance.spent_in_period
+            .checked_add(amount)
+            .ok_or(VaultError::Overflow)?;
+        require!(spent <= self.allowance.max_per_period, VaultError::AllowanceExceeded);
+        self.allowance.spent_in_period = spent;
+
+        self.vault_state.record_withdrawal(amount, self.vault.lamports(), now)?;
+
+        let system_program= self.system_program.to_account_info();
+        let accounts = Transfer {
+            from: self.vault.to_account_info(),
+            to: self.delegate.to_account_info(),
+        };
+
+        let vault_state_key = self.vault_state.key();
+        let seeds = &[
+            b"vault",
+            vault_state_key.as_ref(),
+            &[self.vault_state.vault_bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_context = CpiContext::new_with_signer(system_program, accounts, signer_seeds);
+
+        transfer(cpi_context, amount)?;
+
+        emit!(VaultWithdrawn {
+            vault_state: self.vault_state.key(),
+            recipient: self.delegate.key(),
+            amount,
+            balance: self.vault.lamports(),
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
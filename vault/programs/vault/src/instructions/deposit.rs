@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use crate::{
+    error::VaultError,
+    events::VaultDeposited,
+    state::VaultState,
+    utils::deposit_lamports,
+};
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state", vault_state.creator.as_ref()],
+        bump = vault_state.state_bump,
+        constraint = vault_state.owner == signer.key() @ VaultError::Unauthorized
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault_state.key().as_ref()],
+        bump = vault_state.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Deposit <'info> {
+
+    pub fn deposit(&mut self, amount: u64) -> Result<()>{
+        deposit_lamports(
+            self.system_program.to_account_info(),
+            self.signer.to_account_info(),
+            self.vault.to_account_info(),
+            amount,
+        )?;
+        self.vault_state.record_deposit(amount)?;
+
+        emit!(VaultDeposited {
+            vault_state: self.vault_state.key(),
+            depositor: self.signer.key(),
+            amount,
+            balance: self.vault.lamports(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::{
+    events::VaultDeposited,
+    state::MultisigState,
+    utils::deposit_lamports,
+};
+
+// Anyone can fund a multisig vault, only the owners together can take funds out
+#[derive(Accounts)]
+pub struct DepositMultisig<'info> {
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [b"multisig", multisig_state.creator.as_ref(), multisig_state.seed.to_le_bytes().as_ref()],
+        bump = multisig_state.state_bump
+    )]
+    pub multisig_state: Account<'info, MultisigState>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", multisig_state.key().as_ref()],
+        bump = multisig_state.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> DepositMultisig <'info> {
+
+    pub fn deposit_multisig(&mut self, amount: u64) -> Result<()>{
+        deposit_lamports(
+            self.system_program.to_account_info(),
+            self.signer.to_account_info(),
+            self.vault.to_account_info(),
+            amount,
+        )?;
+
+        emit!(VaultDeposited {
+            vault_state: self.multisig_state.key(),
+            depositor: self.signer.key(),
+            amount,
+            balance: self.vault.lamports(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+use crate::{
+    error::VaultError,
+    state::TokenVaultState,
+};
+
+#[derive(Accounts)]
+pub struct DepositToken<'info> {
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mint::token_program = token_program,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = signer,
+        associated_token::token_program = token_program,
+    )]
+    pub signer_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        has_one = mint,
+        seeds = [b"state", signer.key().as_ref(), mint.key().as_ref()],
+        bump = token_vault_state.state_bump
+    )]
+    pub token_vault_state: Account<'info, TokenVaultState>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = token_vault_state,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> DepositToken <'info> {
+
+    pub fn deposit_token(&mut self, amount: u64) -> Result<()>{
+        require!(amount > 0, VaultError::ZeroAmount);
+
+        let cpi_program = self.token_program.to_account_info();
+        let cpi_accounts = TransferChecked {
+            from: self.signer_ata.to_account_info(),
+            to: self.vault.to_account_info(),
+            mint: self.mint.to_account_info(),
+            authority: self.signer.to_account_info(),
+        };
+
+        let cpi_context = CpiContext::new(cpi_program, cpi_accounts);
+
+        transfer_checked(cpi_context, amount, self.mint.decimals)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+use crate::{
+    error::VaultError,
+    state::{MultisigState, WithdrawalProposal},
+    utils::check_vault_withdrawal,
+};
+
+#[derive(Accounts)]
+pub struct ExecuteWithdrawal<'info> {
+
+    pub owner: Signer<'info>,
+
+    #[account(mut)]
+    pub proposer: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub recipient: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"multisig", multisig_state.creator.as_ref(), multisig_state.seed.to_le_bytes().as_ref()],
+        bump = multisig_state.state_bump,
+        constraint = multisig_state.is_owner(&owner.key()) @ VaultError::NotAnOwner
+    )]
+    pub multisig_state: Account<'info, MultisigState>,
+
+    #[account(
+        mut,
+        close = proposer,
+        has_one = proposer,
+        has_one = recipient,
+        seeds = [b"proposal", multisig_state.key().as_ref(), proposal.index.to_le_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, WithdrawalProposal>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", multisig_state.key().as_ref()],
+        bump = multisig_state.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ExecuteWithdrawal <'info> {
+
+    pub fn execute_withdrawal(&mut self) -> Result<()>{
+        require!(
+            self.proposal.approvals.len() >= self.multisig_state.threshold as usize,
+            VaultError::NotEnoughApprovals
+        );
+        check_vault_withdrawal(&self.vault, self.proposal.amount)?;
+
+        let system_program= self.system_program.to_account_info();
+        let accounts = Transfer {
+            from: self.vault.to_account_info(),
+            to: self.recipient.to_account_info(),
+        };
+
+        let multisig_state_key = self.multisig_state.key();
+        let seeds = &[
+            b"vault",
+            multisig_state_key.as_ref(),
+            &[self.multisig_state.vault_bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_context = CpiContext::new_with_signer(system_program, accounts, signer_seeds);
+
+        transfer(cpi_context, self.proposal.amount)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use crate::{
+    error::VaultError,
+    state::{Allowance, VaultState},
+};
+
+#[derive(Accounts)]
+pub struct GrantAllowance<'info> {
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub delegate: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state", vault_state.creator.as_ref()],
+        bump = vault_state.state_bump,
+        constraint = vault_state.owner == signer.key() @ VaultError::Unauthorized
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + Allowance::INIT_SPACE,
+        seeds = [b"allowance", vault_state.key().as_ref(), delegate.key().as_ref()],
+        bump
+    )]
+    pub allowance: Account<'info, Allowance>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> GrantAllowance <'info> {
+
+    pub fn grant_allowance(
+        &mut self,
+        max_per_period: u64,
+        period: i64,
+        expiry: i64,
+        bumps: GrantAllowanceBumps
+    ) -> Result<()>{
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            max_per_period > 0 && period > 0 && expiry > now,
+            VaultError::InvalidAllowance
+        );
+
+        self.vault_state.open_delegations = self.vault_state.open_delegations
+            .checked_add(1)
+            .ok_or(VaultError::Overflow)?;
+
+        self.allowance.set_inner(Allowance {
+            vault_state: self.vault_state.key(),
+            delegate: self.delegate.key(),
+            max_per_period,
+            period,
+            expiry,
+            period_start: now,
+            spent_in_period: 0,
+            bump: bumps.allowance,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use crate::{
+    error::VaultError,
+    events::{OwnerRotated, RecoveryStarted, VaultFreezeChanged},
+    state::VaultState,
+};
+
+#[derive(Accounts)]
+pub struct GuardianAction<'info> {
+
+    pub guardian: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state", vault_state.creator.as_ref()],
+        bump = vault_state.state_bump,
+        constraint = vault_state.guardian == Some(guardian.key()) @ VaultError::NotGuardian
+    )]
+    pub vault_state: Account<'info, VaultState>,
+}
+
+impl<'info> GuardianAction <'info> {
+
+    pub fn set_frozen(&mut self, frozen: bool) -> Result<()>{
+        self.vault_state.frozen = frozen;
+
+        emit!(VaultFreezeChanged {
+            vault_state: self.vault_state.key(),
+            guardian: self.guardian.key(),
+            frozen,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn initiate_recovery(&mut self, new_owner: Pubkey) -> Result<()>{
+        let now = Clock::get()?.unix_timestamp;
+
+        self.vault_state.pending_owner = Some(new_owner);
+        self.vault_state.recovery_started_at = now;
+
+        emit!(RecoveryStarted {
+            vault_state: self.vault_state.key(),
+            guardian: self.guardian.key(),
+            pending_owner: new_owner,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    pub fn complete_recovery(&mut self) -> Result<()>{
+        let new_owner = self.vault_state.pending_owner.ok_or(VaultError::NoPendingRecovery)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let unlocks_at = self.vault_state.recovery_started_at
+            .checked_add(self.vault_state.recovery_timelock)
+            .ok_or(VaultError::Overflow)?;
+        require!(now >= unlocks_at, VaultError::RecoveryTimelockNotPassed);
+
+        let old_owner = self.vault_state.owner;
+        self.vault_state.owner = new_owner;
+        self.vault_state.pending_owner = None;
+
+        emit!(OwnerRotated {
+            vault_state: self.vault_state.key(),
+            old_owner,
+            new_owner,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
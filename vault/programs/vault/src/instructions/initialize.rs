@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{VaultState, VestingSchedule},
+    utils::new_vault_state,
+};
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + VaultState::INIT_SPACE,
+        seeds = [b"state", signer.key().as_ref()],
+        bump 
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        seeds = [b"vault", vault_state.key().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Initialize <'info> {
+
+    pub fn initialize(
+        &mut self,
+        unlock_timestamp: i64,
+        vesting: Option<VestingSchedule>,
+        bumps: InitializeBumps
+    ) -> Result<()>{
+        self.vault_state.set_inner(new_vault_state(
+            self.signer.key(),
+            unlock_timestamp,
+            vesting,
+            bumps.vault,
+            bumps.vault_state,
+        )?);
+
+        Ok(())
+    }
+}
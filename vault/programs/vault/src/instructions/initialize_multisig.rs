@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::MAX_OWNERS,
+    error::VaultError,
+    state::MultisigState,
+};
+
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct InitializeMultisig<'info> {
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + MultisigState::INIT_SPACE,
+        seeds = [b"multisig", signer.key().as_ref(), seed.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub multisig_state: Account<'info, MultisigState>,
+
+    #[account(
+        seeds = [b"vault", multisig_state.key().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeMultisig <'info> {
+
+    pub fn initialize_multisig(
+        &mut self,
+        seed: u64,
+        owners: Vec<Pubkey>,
+        threshold: u8,
+        bumps: InitializeMultisigBumps
+    ) -> Result<()>{
+        require!(owners.len() <= MAX_OWNERS as usize, VaultError::TooManyOwners);
+        require!(
+            threshold > 0 && threshold as usize <= owners.len(),
+            VaultError::InvalidThreshold
+        );
+        for (i, owner) in owners.iter().enumerate() {
+            require!(!owners[..i].contains(owner), VaultError::DuplicateOwner);
+        }
+
+        self.multisig_state.set_inner(MultisigState {
+            creator: self.signer.key(),
+            seed,
+            owners,
+            threshold,
+            proposal_count: 0,
+            vault_bump: bumps.vault,
+            state_bump: bumps.multisig_state,
+        });
+
+        Ok(())
+    }
+}
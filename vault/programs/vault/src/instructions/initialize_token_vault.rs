@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+use crate::state::TokenVaultState;
+
+#[derive(Accounts)]
+pub struct InitializeTokenVault<'info> {
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mint::token_program = token_program,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + TokenVaultState::INIT_SPACE,
+        seeds = [b"state", signer.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub token_vault_state: Account<'info, TokenVaultState>,
+
+    #[account(
+        init,
+        payer = signer,
+        associated_token::mint = mint,
+        associated_token::authority = token_vault_state,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeTokenVault <'info> {
+
+    pub fn initialize_token_vault(&mut self, bumps: InitializeTokenVaultBumps) -> Result<()>{
+        self.token_vault_state.set_inner(TokenVaultState {
+            mint: self.mint.key(),
+            state_bump: bumps.token_vault_state,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+pub mod initialize;
+pub mod deposit;
+pub mod withdraw;
+pub mod close;
+pub mod reopen;
+pub mod initialize_token_vault;
+pub mod deposit_token;
+pub mod withdraw_token;
+pub mod close_token_vault;
+pub mod initialize_multisig;
+pub mod deposit_multisig;
+pub mod propose_withdrawal;
+pub mod approve_withdrawal;
+pub mod execute_withdrawal;
+pub mod grant_allowance;
+pub mod revoke_allowance;
+pub mod delegated_withdraw;
+pub mod create_stream;
+pub mod claim_stream;
+pub mod cancel_stream;
+pub mod owner_action;
+pub mod guardian_action;
+
+pub use initialize::*;
+pub use deposit::*;
+pub use withdraw::*;
+pub use close::*;
+pub use reopen::*;
+pub use initialize_token_vault::*;
+pub use deposit_token::*;
+pub use withdraw_token::*;
+pub use close_token_vault::*;
+pub use initialize_multisig::*;
+pub use deposit_multisig::*;
+pub use propose_withdrawal::*;
+pub use approve_withdrawal::*;
+pub use execute_withdrawal::*;
+pub use grant_allowance::*;
+pub use revoke_allowance::*;
+pub use delegated_withdraw::*;
+pub use create_stream::*;
+pub use claim_stream::*;
+pub use cancel_stream::*;
+pub use owner_action::*;
+pub use guardian_action::*;
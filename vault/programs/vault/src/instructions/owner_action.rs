@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::MIN_RECOVERY_TIMELOCK,
+    error::VaultError,
+    events::{GuardianChangeStarted, GuardianSet, RecoveryCancelled},
+    state::{GuardianChange, VaultState},
+};
+
+#[derive(Accounts)]
+pub struct OwnerAction<'info> {
+
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state", vault_state.creator.as_ref()],
+        bump = vault_state.state_bump,
+        constraint = vault_state.owner == signer.key() @ VaultError::Unauthorized
+    )]
+    pub vault_state: Account<'info, VaultState>,
+}
+
+impl<'info> OwnerAction <'info> {
+
+    // Takes effect right away, unless the vault is frozen. Then the change only starts and the
+    // owner completes it one recovery timelock later, so a lost or hostile guardian can't hold
+    // the funds forever but can't be swapped out from under an ongoing freeze either.
+    pub fn set_guardian(&mut self, guardian: Option<Pubkey>, recovery_timelock: i64) -> Result<()>{
+        if guardian.is_some() {
+            require!(
+                recovery_timelock >= MIN_RECOVERY_TIMELOCK,
+                VaultError::RecoveryTimelockTooShort
+            );
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+
+        if self.vault_state.frozen {
+            self.vault_state.guardian_change = Some(GuardianChange {
+                guardian,
+                recovery_timelock,
+                started_at: now,
+            });
+
+            emit!(GuardianChangeStarted {
+                vault_state: self.vault_state.key(),
+                guardian,
+                recovery_timelock,
+                timestamp: now,
+            });
+
+            return Ok(());
+        }
+
+        self.apply_guardian(guardian, recovery_timelock, now)
+    }
+
+    // Completes a guardian change started while the vault was frozen, and unfreezes it
+    pub fn complete_guardian_change(&mut self) -> Result<()>{
+        let change = self.vault_state.guardian_change.ok_or(VaultError::NoPendingGuardianChange)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let unlocks_at = change.started_at
+            .checked_add(self.vault_state.recovery_timelock)
+            .ok_or(VaultError::Overflow)?;
+        require!(now >= unlocks_at, VaultError::GuardianChangeTimelockNotPassed);
+
+        self.vault_state.frozen = false;
+        self.apply_guardian(change.guardian, change.recovery_timelock, now)
+    }
+
+    fn apply_guardian(&mut self, guardian: Option<Pubkey>, recovery_timelock: i64, now: i64) -> Result<()>{
+        self.vault_state.guardian = guardian;
+        self.vault_state.recovery_timelock = recovery_timelock;
+        self.vault_state.pending_owner = None;
+        self.vault_state.guardian_change = None;
+
+        emit!(GuardianSet {
+            vault_state: self.vault_state.key(),
+            guardian,
+            recovery_timelock,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    // The owner challenges a recovery by cancelling it before its timelock runs out
+    pub fn cancel_recovery(&mut self) -> Result<()>{
+        require!(self.vault_state.pending_owner.is_some(), VaultError::NoPendingRecovery);
+
+        self.vault_state.pending_owner = None;
+
+        emit!(RecoveryCancelled {
+            vault_state: self.vault_state.key(),
+            owner: self.signer.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::{
+    error::VaultError,
+    state::{MultisigState, WithdrawalProposal},
+};
+
+#[derive(Accounts)]
+pub struct ProposeWithdrawal<'info> {
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"multisig", multisig_state.creator.as_ref(), multisig_state.seed.to_le_bytes().as_ref()],
+        bump = multisig_state.state_bump,
+        constraint = multisig_state.is_owner(&proposer.key()) @ VaultError::NotAnOwner
+    )]
+    pub multisig_state: Account<'info, MultisigState>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + WithdrawalProposal::INIT_SPACE,
+        seeds = [b"proposal", multisig_state.key().as_ref(), multisig_state.proposal_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, WithdrawalProposal>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ProposeWithdrawal <'info> {
+
+    pub fn propose_withdrawal(
+        &mut self,
+        recipient: Pubkey,
+        amount: u64,
+        bumps: ProposeWithdrawalBumps
+    ) -> Result<()>{
+        require!(amount > 0, VaultError::ZeroAmount);
+
+        let index = self.multisig_state.proposal_count;
+
+        // The proposer's own approval is recorded straight away
+        self.proposal.set_inner(WithdrawalProposal {
+            multisig: self.multisig_state.key(),
+            proposer: self.proposer.key(),
+            recipient,
+            amount,
+            index,
+            approvals: vec![self.proposer.key()],
+            bump: bumps.proposal,
+        });
+
+        self.multisig_state.proposal_count = index
+            .checked_add(1)
+            .ok_or(VaultError::Overflow)?;
+
+        Ok(())
+    }
+}
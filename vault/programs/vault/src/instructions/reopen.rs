@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use crate::{
+    events::VaultReopened,
+    state::{VaultState, VestingSchedule},
+    utils::{deposit_lamports, new_vault_state},
+};
+
+#[derive(Accounts)]
+pub struct Reopen<'info> {
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + VaultState::INIT_SPACE,
+        seeds = [b"state", signer.key().as_ref()],
+        bump 
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault_state.key().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Reopen <'info> {
+
+    // Recreates a vault torn down by `close` and funds it in the same instruction
+    pub fn reopen(
+        &mut self,
+        amount: u64,
+        unlock_timestamp: i64,
+        vesting: Option<VestingSchedule>,
+        bumps: ReopenBumps
+    ) -> Result<()>{
+        self.vault_state.set_inner(new_vault_state(
+            self.signer.key(),
+            unlock_timestamp,
+            vesting,
+            bumps.vault,
+            bumps.vault_state,
+        )?);
+
+        deposit_lamports(
+            self.system_program.to_account_info(),
+            self.signer.to_account_info(),
+            self.vault.to_account_info(),
+            amount,
+        )?;
+        self.vault_state.record_deposit(amount)?;
+
+        emit!(VaultReopened {
+            vault_state: self.vault_state.key(),
+            owner: self.signer.key(),
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::{
+    error::VaultError,
+    state::{Allowance, VaultState},
+};
+
+#[derive(Accounts)]
+pub struct RevokeAllowance<'info> {
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state", vault_state.creator.as_ref()],
+        bump = vault_state.state_bump,
+        constraint = vault_state.owner == signer.key() @ VaultError::Unauthorized
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        close = signer,
+        seeds = [b"allowance", vault_state.key().as_ref(), allowance.delegate.as_ref()],
+        bump = allowance.bump
+    )]
+    pub allowance: Account<'info, Allowance>,
+}
+
+impl<'info> RevokeAllowance <'info> {
+
+    // The allowance account itself is closed by the constraints
+    pub fn revoke_allowance(&mut self) -> Result<()>{
+        self.vault_state.open_delegations = self.vault_state.open_delegations.saturating_sub(1);
+        Ok(())
+    }
+}
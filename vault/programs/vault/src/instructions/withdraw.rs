@@ -0,0 +1,69 @@
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+use crate::{
+    error::VaultError,
+    events::VaultWithdrawn,
+    state::VaultState,
+    utils::check_vault_withdrawal,
+};
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state", vault_state.creator.as_ref()],
+        bump = vault_state.state_bump,
+        constraint = vault_state.owner == signer.key() @ VaultError::Unauthorized
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault_state.key().as_ref()],
+        bump = vault_state.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Withdraw <'info> {
+
+    pub fn withdraw(&mut self, amount: u64) -> Result<()>{
+        check_vault_withdrawal(&self.vault, amount)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        self.vault_state.record_withdrawal(amount, self.vault.lamports(), now)?;
+
+        let system_program= self.system_program.to_account_info();
+        let accounts = Transfer {
+            from: self.vault.to_account_info(),
+            to: self.signer.to_account_info(),
+        };
+
+        let vault_bump = self.vault_state.vault_bump;
+        let vault_state_key = self.vault_state.to_account_info().key;
+        let seeds = &[
+            b"vault", 
+            vault_state_key.as_ref(), 
+            &[vault_bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_context = CpiContext::new_with_signer(system_program, accounts, signer_seeds);
+
+        transfer(cpi_context, amount)?;
+
+        emit!(VaultWithdrawn {
+            vault_state: self.vault_state.key(),
+            recipient: self.signer.key(),
+            amount,
+            balance: self.vault.lamports(),
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
@@ -1,4 +1,15 @@
-use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+pub mod constants;
+pub mod error;
+pub mod events;
+pub mod instructions;
+pub mod state;
+pub mod utils;
+
+use anchor_lang::prelude::*;
+
+pub use constants::*;
+pub use instructions::*;
+pub use state::*;
 
 declare_id!("BfbSEiML6f23QyDjsFjUBUCnAHXFSq9Hmtg2nSLdXP3N");
 
@@ -6,195 +17,144 @@ declare_id!("BfbSEiML6f23QyDjsFjUBUCnAHXFSq9Hmtg2nSLdXP3N");
 pub mod vault {
     use super::*;
 
-    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
-        let _ = ctx.accounts.initialize(ctx.bumps);
-        Ok(())
+    pub fn initialize(
+        ctx: Context<Initialize>,
+        unlock_timestamp: i64,
+        vesting: Option<VestingSchedule>
+    ) -> Result<()> {
+        ctx.accounts.initialize(unlock_timestamp, vesting, ctx.bumps)
     }
 
     pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
-        let _ = ctx.accounts.deposit(amount);
-        Ok(())
+        ctx.accounts.deposit(amount)
     }
 
     pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
-        let _ = ctx.accounts.withdraw(amount);
-        Ok(())
+        ctx.accounts.withdraw(amount)
     }
 
     pub fn close(ctx: Context<Close>) -> Result<()> {
-        let _ = ctx.accounts.close();
-        Ok(())
+        ctx.accounts.close()
     }
-}
-
-#[account]
-#[derive(InitSpace)]
-pub struct VaultState{
-    pub vault_bump: u8,
-    pub state_bump: u8
-}
-
-#[derive(Accounts)]
-pub struct Initialize<'info> {
-
-    #[account(mut)]
-    pub signer: Signer<'info>,
-
-    #[account(
-        init,
-        payer = signer,
-        space = 8 + VaultState::INIT_SPACE,
-        seeds = [b"state", signer.key().as_ref()],
-        bump 
-    )]
-    pub vault_state: Account<'info, VaultState>,
-
-    #[account(
-        seeds = [b"vault", vault_state.key().as_ref()],
-        bump
-    )]
-    pub vault: SystemAccount<'info>,
-
-    pub system_program: Program<'info, System>,
-}
-
-impl<'info> Initialize <'info> {
 
-    pub fn initialize(&mut self, bumps: InitializeBumps) -> Result<()>{
-        self.vault_state.vault_bump = bumps.vault;
-        self.vault_state.state_bump = bumps.vault_state;
-
-        Ok(())
+    pub fn reopen(
+        ctx: Context<Reopen>,
+        amount: u64,
+        unlock_timestamp: i64,
+        vesting: Option<VestingSchedule>
+    ) -> Result<()> {
+        ctx.accounts.reopen(amount, unlock_timestamp, vesting, ctx.bumps)
     }
-}
-
-#[derive(Accounts)]
-pub struct Deposit<'info> {
-
-    #[account(mut)]
-    pub signer: Signer<'info>,
-
-    #[account(
-        seeds = [b"state", signer.key().as_ref()],
-        bump = vault_state.state_bump
-    )]
-    pub vault_state: Account<'info, VaultState>,
-
-    #[account(
-        mut,
-        seeds = [b"vault", vault_state.key().as_ref()],
-        bump = vault_state.vault_bump
-    )]
-    pub vault: SystemAccount<'info>,
 
-    pub system_program: Program<'info, System>,
-}
-
-impl<'info> Deposit <'info> {
-
-    pub fn deposit(&mut self, amount: u64) -> Result<()>{
-        let system_program= self.system_program.to_account_info();
-        let accounts = Transfer {
-            from: self.signer.to_account_info(),
-            to: self.vault.to_account_info(),
-        };
-
-        let cpi_context = CpiContext::new(system_program, accounts);
+    pub fn initialize_token_vault(ctx: Context<InitializeTokenVault>) -> Result<()> {
+        ctx.accounts.initialize_token_vault(ctx.bumps)
+    }
 
-        transfer(cpi_context, amount)?;
-        Ok(())
+    pub fn deposit_token(ctx: Context<DepositToken>, amount: u64) -> Result<()> {
+        ctx.accounts.deposit_token(amount)
     }
-}
 
-#[derive(Accounts)]
-pub struct Withdraw<'info> {
+    pub fn withdraw_token(ctx: Context<WithdrawToken>, amount: u64) -> Result<()> {
+        ctx.accounts.withdraw_token(amount)
+    }
 
-    #[account(mut)]
-    pub signer: Signer<'info>,
+    pub fn close_token_vault(ctx: Context<CloseTokenVault>) -> Result<()> {
+        ctx.accounts.close_token_vault()
+    }
 
-    #[account(
-        seeds = [b"state", signer.key().as_ref()],
-        bump = vault_state.state_bump
-    )]
-    pub vault_state: Account<'info, VaultState>,
+    pub fn initialize_multisig(
+        ctx: Context<InitializeMultisig>,
+        seed: u64,
+        owners: Vec<Pubkey>,
+        threshold: u8
+    ) -> Result<()> {
+        ctx.accounts.initialize_multisig(seed, owners, threshold, ctx.bumps)
+    }
 
-    #[account(
-        mut,
-        seeds = [b"vault", vault_state.key().as_ref()],
-        bump = vault_state.vault_bump
-    )]
-    pub vault: SystemAccount<'info>,
+    pub fn deposit_multisig(ctx: Context<DepositMultisig>, amount: u64) -> Result<()> {
+        ctx.accounts.deposit_multisig(amount)
+    }
 
-    pub system_program: Program<'info, System>,
-}
+    pub fn propose_withdrawal(
+        ctx: Context<ProposeWithdrawal>,
+        recipient: Pubkey,
+        amount: u64
+    ) -> Result<()> {
+        ctx.accounts.propose_withdrawal(recipient, amount, ctx.bumps)
+    }
 
-impl<'info> Withdraw <'info> {
+    pub fn approve_withdrawal(ctx: Context<ApproveWithdrawal>) -> Result<()> {
+        ctx.accounts.approve_withdrawal()
+    }
 
-    pub fn withdraw(&mut self, amount: u64) -> Result<()>{
-        let system_program= self.system_program.to_account_info();
-        let accounts = Transfer {
-            from: self.vault.to_account_info(),
-            to: self.signer.to_account_info(),
-        };
+    pub fn execute_withdrawal(ctx: Context<ExecuteWithdrawal>) -> Result<()> {
+        ctx.accounts.execute_withdrawal()
+    }
 
-        let vault_bump = self.vault_state.vault_bump;
-        let vault_state_key = self.vault_state.to_account_info().key;
-        let seeds = &[
-            b"vault", 
-            vault_state_key.as_ref(), 
-            &[vault_bump]];
-        let signer_seeds = &[&seeds[..]];
+    pub fn grant_allowance(
+        ctx: Context<GrantAllowance>,
+        max_per_period: u64,
+        period: i64,
+        expiry: i64
+    ) -> Result<()> {
+        ctx.accounts.grant_allowance(max_per_period, period, expiry, ctx.bumps)
+    }
 
-        let cpi_context = CpiContext::new_with_signer(system_program, accounts, signer_seeds);
+    pub fn revoke_allowance(ctx: Context<RevokeAllowance>) -> Result<()> {
+        ctx.accounts.revoke_allowance()
+    }
 
-        transfer(cpi_context, amount)?;
-        Ok(())
+    pub fn delegated_withdraw(ctx: Context<DelegatedWithdraw>, amount: u64) -> Result<()> {
+        ctx.accounts.delegated_withdraw(amount)
     }
-}
 
-#[derive(Accounts)]
-pub struct Close <'info> {
+    pub fn create_stream(
+        ctx: Context<CreateStream>,
+        seed: u64,
+        rate: u64,
+        start: i64,
+        end: i64
+    ) -> Result<()> {
+        ctx.accounts.create_stream(seed, rate, start, end, ctx.bumps)
+    }
 
-    #[account(mut)]
-    pub signer: Signer<'info>,
+    pub fn claim_stream(ctx: Context<ClaimStream>) -> Result<()> {
+        ctx.accounts.claim_stream()
+    }
 
-    #[account(
-        mut,
-        seeds = [b"state", signer.key().as_ref()],
-        bump = vault_state.state_bump
-    )]
-    pub vault_state: Account<'info, VaultState>,
+    pub fn cancel_stream(ctx: Context<CancelStream>) -> Result<()> {
+        ctx.accounts.cancel_stream()
+    }
 
-    #[account(
-        mut,
-        seeds = [b"vault",vault_state.key().as_ref()],
-        bump = vault_state.vault_bump
-    )]
-    pub vault: SystemAccount<'info>,
+    pub fn set_guardian(
+        ctx: Context<OwnerAction>,
+        guardian: Option<Pubkey>,
+        recovery_timelock: i64
+    ) -> Result<()> {
+        ctx.accounts.set_guardian(guardian, recovery_timelock)
+    }
 
-    pub system_program: Program<'info, System>,
-}
+    pub fn complete_guardian_change(ctx: Context<OwnerAction>) -> Result<()> {
+        ctx.accounts.complete_guardian_change()
+    }
 
-impl<'info> Close <'info> {
+    pub fn cancel_recovery(ctx: Context<OwnerAction>) -> Result<()> {
+        ctx.accounts.cancel_recovery()
+    }
 
-    pub fn close(&mut self) -> Result<()>{
-        let system_program= self.system_program.to_account_info();
-        let accounts = Transfer {
-            from: self.vault.to_account_info(),
-            to: self.signer.to_account_info(),
-        };
+    pub fn freeze(ctx: Context<GuardianAction>) -> Result<()> {
+        ctx.accounts.set_frozen(true)
+    }
 
-        let vault_bump = self.vault_state.vault_bump;
-        let vault_state_key = self.vault_state.to_account_info().key;
-        let seeds = &[
-            b"vault", 
-            vault_state_key.as_ref(), 
-            &[vault_bump]];
-        let signer_seeds = &[&seeds[..]];
+    pub fn unfreeze(ctx: Context<GuardianAction>) -> Result<()> {
+        ctx.accounts.set_frozen(false)
+    }
 
-        let cpi_context = CpiContext::new_with_signer(system_program, accounts, signer_seeds);
+    pub fn initiate_recovery(ctx: Context<GuardianAction>, new_owner: Pubkey) -> Result<()> {
+        ctx.accounts.initiate_recovery(new_owner)
+    }
 
-        transfer(cpi_context, self.vault.lamports())?;
-        Ok(())
+    pub fn complete_recovery(ctx: Context<GuardianAction>) -> Result<()> {
+        ctx.accounts.complete_recovery()
     }
 }
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Allowance{
+    pub vault_state: Pubkey, // vault the delegate may withdraw from
+    pub delegate: Pubkey, // key allowed to call `delegated_withdraw`
+    pub max_per_period: u64, // lamports the delegate may take per period
+    pub period: i64, // length of a spending period in seconds
+    pub expiry: i64, // the allowance can't be used from this time on
+    pub period_start: i64, // start of the current spending period
+    pub spent_in_period: u64, // lamports taken during the current period
+    pub bump: u8
+}
+
+impl Allowance {
+
+    // Rolls the spending window forward if `now` is past the current period
+    pub fn refresh_period(&mut self, now: i64) {
+        let elapsed = now - self.period_start;
+        if elapsed >= self.period {
+            self.period_start += elapsed - elapsed % self.period;
+            self.spent_in_period = 0;
+        }
+    }
+}
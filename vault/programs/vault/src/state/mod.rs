@@ -0,0 +1,13 @@
+pub mod vault_state;
+pub mod token_vault_state;
+pub mod multisig_state;
+pub mod withdrawal_proposal;
+pub mod allowance;
+pub mod stream;
+
+pub use vault_state::*;
+pub use token_vault_state::*;
+pub use multisig_state::*;
+pub use withdrawal_proposal::*;
+pub use allowance::*;
+pub use stream::*;
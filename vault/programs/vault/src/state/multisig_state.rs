@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_OWNERS;
+
+#[account]
+#[derive(InitSpace)]
+pub struct MultisigState{
+    pub creator: Pubkey, // signer that created the multisig, part of its seeds
+    pub seed: u64, // seed to create several multisigs per creator
+    #[max_len(MAX_OWNERS)]
+    pub owners: Vec<Pubkey>, // keys allowed to propose and approve withdrawals
+    pub threshold: u8, // approvals needed before a withdrawal can be executed
+    pub proposal_count: u64, // index of the next withdrawal proposal
+    pub vault_bump: u8,
+    pub state_bump: u8
+}
+
+impl MultisigState {
+    pub fn is_owner(&self, key: &Pubkey) -> bool {
+        self.owners.contains(key)
+    }
+}
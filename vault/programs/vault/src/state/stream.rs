@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Stream{
+    pub vault_state: Pubkey, // vault paying the stream
+    pub recipient: Pubkey, // key allowed to claim the stream
+    pub seed: u64, // seed to create several streams per recipient
+    pub rate: u64, // lamports accrued per second
+    pub start: i64, // the stream starts accruing at this time
+    pub end: i64, // the stream stops accruing at this time
+    pub last_claimed: i64, // accrual already paid out up to this time
+    pub bump: u8
+}
+
+impl Stream {
+
+    // Pays out the accrual since the last claim, in whole seconds of the stream and up to
+    // `available`, and returns the amount. Accrual the vault can't cover yet stays claimable.
+    pub fn settle(&mut self, now: i64, available: u64) -> u64 {
+        let from = self.last_claimed.max(self.start);
+        let to = now.min(self.end);
+        if to <= from {
+            return 0;
+        }
+
+        let seconds = ((to - from) as u64).min(available / self.rate);
+        self.last_claimed = from + seconds as i64;
+
+        // Can't overflow, it is at most `available`
+        self.rate * seconds
+    }
+}
@@ -0,0 +1,8 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct TokenVaultState{
+    pub mint: Pubkey, // mint held by this vault's token account
+    pub state_bump: u8
+}
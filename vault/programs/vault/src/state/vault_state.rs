@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use crate::error::VaultError;
+
+#[account]
+#[derive(InitSpace)]
+pub struct VaultState{
+    pub creator: Pubkey, // signer that created the vault, part of its seeds
+    pub owner: Pubkey, // key allowed to move funds, the guardian can rotate it
+    pub vault_bump: u8,
+    pub state_bump: u8,
+    pub unlock_timestamp: i64, // nothing can leave the vault before this time
+    pub vesting: Option<VestingSchedule>, // optional linear release of the deposits
+    pub total_deposited: u64, // lifetime deposits, the base the vesting schedule releases
+    pub total_withdrawn: u64, // lifetime withdrawals, subtracted from the vested amount
+    pub guardian: Option<Pubkey>, // key allowed to freeze the vault and recover its ownership
+    pub frozen: bool, // if nothing can leave the vault
+    pub recovery_timelock: i64, // seconds the owner has to challenge a recovery
+    pub pending_owner: Option<Pubkey>, // owner proposed by an ongoing recovery
+    pub recovery_started_at: i64, // when the ongoing recovery was started
+    pub open_delegations: u32, // allowances and streams not closed yet, the vault can't be closed before they are
+    pub guardian_change: Option<GuardianChange>, // guardian the owner is swapping in while the vault is frozen
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct GuardianChange {
+    pub guardian: Option<Pubkey>, // guardian to set, None to remove it
+    pub recovery_timelock: i64, // recovery timelock that comes with it
+    pub started_at: i64, // the change can complete one recovery timelock after this
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct VestingSchedule {
+    pub start: i64, // vesting starts accruing at this time
+    pub cliff: i64, // nothing is released before this time
+    pub end: i64, // everything is released from this time on
+}
+
+impl VestingSchedule {
+    pub fn is_valid(&self) -> bool {
+        self.start <= self.cliff && self.cliff <= self.end && self.start < self.end
+    }
+}
+
+impl VaultState {
+
+    pub fn new(
+        owner: Pubkey,
+        unlock_timestamp: i64,
+        vesting: Option<VestingSchedule>,
+        vault_bump: u8,
+        state_bump: u8
+    ) -> Self {
+        Self {
+            creator: owner,
+            owner,
+            vault_bump,
+            state_bump,
+            unlock_timestamp,
+            vesting,
+            total_deposited: 0,
+            total_withdrawn: 0,
+            guardian: None,
+            frozen: false,
+            recovery_timelock: 0,
+            pending_owner: None,
+            recovery_started_at: 0,
+            open_delegations: 0,
+            guardian_change: None,
+        }
+    }
+
+    // Amount of the vault `balance` that can be taken out at `now`
+    pub fn unlocked_amount(&self, balance: u64, now: i64) -> u64 {
+        if now < self.unlock_timestamp {
+            return 0;
+        }
+
+        let vesting = match self.vesting {
+            Some(vesting) => vesting,
+            None => return balance,
+        };
+
+        if now < vesting.cliff {
+            return 0;
+        }
+        if now >= vesting.end {
+            return balance;
+        }
+
+        let vested = (self.total_deposited as u128)
+            .saturating_mul((now - vesting.start) as u128)
+            / (vesting.end - vesting.start) as u128;
+
+        (vested as u64)
+            .saturating_sub(self.total_withdrawn)
+            .min(balance)
+    }
+
+    // Books `amount` reaching the vault, the base the vesting schedule releases from
+    pub fn record_deposit(&mut self, amount: u64) -> Result<()> {
+        self.total_deposited = self.total_deposited
+            .checked_add(amount)
+            .ok_or(VaultError::Overflow)?;
+
+        Ok(())
+    }
+
+    // Books `amount` leaving a vault holding `balance`, failing if it is frozen or not unlocked yet
+    pub fn record_withdrawal(&mut self, amount: u64, balance: u64, now: i64) -> Result<()> {
+        require!(!self.frozen, VaultError::VaultFrozen);
+        require!(amount <= self.unlocked_amount(balance, now), VaultError::FundsLocked);
+
+        self.total_withdrawn = self.total_withdrawn
+            .checked_add(amount)
+            .ok_or(VaultError::Overflow)?;
+
+        Ok(())
+    }
+}
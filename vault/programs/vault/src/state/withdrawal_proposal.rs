@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_OWNERS;
+
+#[account]
+#[derive(InitSpace)]
+pub struct WithdrawalProposal{
+    pub multisig: Pubkey, // multisig whose vault pays out
+    pub proposer: Pubkey, // owner that created the proposal, gets the rent back
+    pub recipient: Pubkey, // account receiving the lamports
+    pub amount: u64, // lamports to send
+    pub index: u64, // position in the multisig's proposals, part of the seeds
+    #[max_len(MAX_OWNERS)]
+    pub approvals: Vec<Pubkey>, // owners that approved so far
+    pub bump: u8
+}
@@ -0,0 +1,69 @@
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+use crate::{
+    error::VaultError,
+    state::{VaultState, VestingSchedule},
+};
+
+// Checks that `amount` can leave a lamport vault without dropping it below rent exemption.
+// Only `close` may take a vault all the way down to zero.
+pub fn check_vault_withdrawal(vault: &AccountInfo, amount: u64) -> Result<()> {
+    require!(amount > 0, VaultError::ZeroAmount);
+
+    let balance = vault.lamports();
+    require!(amount <= balance, VaultError::InsufficientFunds);
+
+    let rent_exempt = Rent::get()?.minimum_balance(vault.data_len());
+    require!(balance - amount >= rent_exempt, VaultError::RentExemptViolation);
+
+    Ok(())
+}
+
+// Lamports that can leave a lamport vault at `now`: nothing while it is frozen, otherwise
+// what the time lock and vesting release, short of the rent exemption
+pub fn available_to_withdraw(vault_state: &VaultState, vault: &AccountInfo, now: i64) -> Result<u64> {
+    if vault_state.frozen {
+        return Ok(0);
+    }
+
+    let balance = vault.lamports();
+    let rent_exempt = Rent::get()?.minimum_balance(vault.data_len());
+
+    Ok(vault_state
+        .unlocked_amount(balance, now)
+        .min(balance.saturating_sub(rent_exempt)))
+}
+
+// State of a new lamport vault owned by `owner`. `initialize` and `reopen` both go through
+// here so they always set the vault up the same way.
+pub fn new_vault_state(
+    owner: Pubkey,
+    unlock_timestamp: i64,
+    vesting: Option<VestingSchedule>,
+    vault_bump: u8,
+    state_bump: u8,
+) -> Result<VaultState> {
+    if let Some(schedule) = vesting {
+        require!(schedule.is_valid(), VaultError::InvalidVestingSchedule);
+    }
+
+    Ok(VaultState::new(owner, unlock_timestamp, vesting, vault_bump, state_bump))
+}
+
+// Moves `amount` lamports from a signer into a vault
+pub fn deposit_lamports<'info>(
+    system_program: AccountInfo<'info>,
+    from: AccountInfo<'info>,
+    vault: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    require!(amount > 0, VaultError::ZeroAmount);
+
+    let accounts = Transfer {
+        from,
+        to: vault,
+    };
+
+    let cpi_context = CpiContext::new(system_program, accounts);
+
+    transfer(cpi_context, amount)
+}
@@ -0,0 +1,458 @@
+// Runs the compiled program inside LiteSVM, no validator needed.
+// Build it first and enable the feature:
+//   anchor build && cargo test --features test-sbf
+#![cfg(feature = "test-sbf")]
+#![allow(clippy::result_large_err)]
+
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::{clock::Clock, instruction::Instruction, system_program},
+    AccountDeserialize, InstructionData, ToAccountMetas,
+};
+use litesvm::{types::TransactionResult, LiteSVM};
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use vault::{error::VaultError, VaultState, VestingSchedule, MIN_RECOVERY_TIMELOCK};
+
+const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+struct Harness {
+    svm: LiteSVM,
+    owner: Keypair,
+}
+
+impl Harness {
+    fn new() -> Self {
+        let mut svm = LiteSVM::new();
+        svm.add_program_from_file(
+            vault::ID,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/../../target/deploy/vault.so"),
+        )
+        .expect("vault.so not found, run `anchor build` first");
+
+        let owner = Keypair::new();
+        svm.airdrop(&owner.pubkey(), 10 * LAMPORTS_PER_SOL).unwrap();
+
+        Self { svm, owner }
+    }
+
+    fn vault_state(&self) -> Pubkey {
+        Pubkey::find_program_address(&[b"state", self.owner.pubkey().as_ref()], &vault::ID).0
+    }
+
+    fn vault(&self) -> Pubkey {
+        Pubkey::find_program_address(&[b"vault", self.vault_state().as_ref()], &vault::ID).0
+    }
+
+    fn send(&mut self, ix: Instruction, signer: &Keypair) -> TransactionResult {
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&signer.pubkey()),
+            &[signer],
+            self.svm.latest_blockhash(),
+        );
+        let result = self.svm.send_transaction(tx);
+        self.svm.expire_blockhash();
+        result
+    }
+
+    fn warp_to(&mut self, unix_timestamp: i64) {
+        let mut clock = self.svm.get_sysvar::<Clock>();
+        clock.unix_timestamp = unix_timestamp;
+        self.svm.set_sysvar(&clock);
+    }
+
+    fn initialize(&mut self, unlock_timestamp: i64, vesting: Option<VestingSchedule>) -> TransactionResult {
+        let ix = Instruction {
+            program_id: vault::ID,
+            accounts: vault::accounts::Initialize {
+                signer: self.owner.pubkey(),
+                vault_state: self.vault_state(),
+                vault: self.vault(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: vault::instruction::Initialize { unlock_timestamp, vesting }.data(),
+        };
+        let owner = self.owner.insecure_clone();
+        self.send(ix, &owner)
+    }
+
+    fn deposit(&mut self, amount: u64) -> TransactionResult {
+        let ix = Instruction {
+            program_id: vault::ID,
+            accounts: vault::accounts::Deposit {
+                signer: self.owner.pubkey(),
+                vault_state: self.vault_state(),
+                vault: self.vault(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: vault::instruction::Deposit { amount }.data(),
+        };
+        let owner = self.owner.insecure_clone();
+        self.send(ix, &owner)
+    }
+
+    fn withdraw_with(&mut self, signer: &Keypair, vault_state: Pubkey, vault: Pubkey, amount: u64) -> TransactionResult {
+        let ix = Instruction {
+            program_id: vault::ID,
+            accounts: vault::accounts::Withdraw {
+                signer: signer.pubkey(),
+                vault_state,
+                vault,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: vault::instruction::Withdraw { amount }.data(),
+        };
+        self.send(ix, signer)
+    }
+
+    fn withdraw(&mut self, amount: u64) -> TransactionResult {
+        let owner = self.owner.insecure_clone();
+        let (vault_state, vault) = (self.vault_state(), self.vault());
+        self.withdraw_with(&owner, vault_state, vault, amount)
+    }
+
+    fn close(&mut self) -> TransactionResult {
+        let ix = Instruction {
+            program_id: vault::ID,
+            accounts: vault::accounts::Close {
+                signer: self.owner.pubkey(),
+                vault_state: self.vault_state(),
+                vault: self.vault(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: vault::instruction::Close {}.data(),
+        };
+        let owner = self.owner.insecure_clone();
+        self.send(ix, &owner)
+    }
+
+    fn stream(&self, recipient: &Pubkey, seed: u64) -> Pubkey {
+        let vault_state = self.vault_state();
+        Pubkey::find_program_address(
+            &[b"stream", vault_state.as_ref(), recipient.as_ref(), seed.to_le_bytes().as_ref()],
+            &vault::ID,
+        )
+        .0
+    }
+
+    fn create_stream(&mut self, recipient: &Pubkey, rate: u64, start: i64, end: i64) -> TransactionResult {
+        let ix = Instruction {
+            program_id: vault::ID,
+            accounts: vault::accounts::CreateStream {
+                signer: self.owner.pubkey(),
+                recipient: *recipient,
+                vault_state: self.vault_state(),
+                stream: self.stream(recipient, 0),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: vault::instruction::CreateStream { seed: 0, rate, start, end }.data(),
+        };
+        let owner = self.owner.insecure_clone();
+        self.send(ix, &owner)
+    }
+
+    fn cancel_stream(&mut self, recipient: &Pubkey) -> TransactionResult {
+        let ix = Instruction {
+            program_id: vault::ID,
+            accounts: vault::accounts::CancelStream {
+                signer: self.owner.pubkey(),
+                recipient: *recipient,
+                vault_state: self.vault_state(),
+                stream: self.stream(recipient, 0),
+                vault: self.vault(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: vault::instruction::CancelStream {}.data(),
+        };
+        let owner = self.owner.insecure_clone();
+        self.send(ix, &owner)
+    }
+
+    fn owner_action(&mut self, data: Vec<u8>) -> TransactionResult {
+        let ix = Instruction {
+            program_id: vault::ID,
+            accounts: vault::accounts::OwnerAction {
+                signer: self.owner.pubkey(),
+                vault_state: self.vault_state(),
+            }
+            .to_account_metas(None),
+            data,
+        };
+        let owner = self.owner.insecure_clone();
+        self.send(ix, &owner)
+    }
+
+    fn guardian_action(&mut self, guardian: &Keypair, data: Vec<u8>) -> TransactionResult {
+        let ix = Instruction {
+            program_id: vault::ID,
+            accounts: vault::accounts::GuardianAction {
+                guardian: guardian.pubkey(),
+                vault_state: self.vault_state(),
+            }
+            .to_account_metas(None),
+            data,
+        };
+        self.send(ix, guardian)
+    }
+
+    fn balance(&self, key: &Pubkey) -> u64 {
+        self.svm.get_balance(key).unwrap_or(0)
+    }
+
+    fn state(&self) -> VaultState {
+        let account = self.svm.get_account(&self.vault_state()).unwrap();
+        VaultState::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+}
+
+fn assert_vault_error(result: TransactionResult, error: VaultError) {
+    let err = result.expect_err("transaction should have failed").err;
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(0, InstructionError::Custom(error.into()))
+    );
+}
+
+fn assert_anchor_error(result: TransactionResult, error: anchor_lang::error::ErrorCode) {
+    let err = result.expect_err("transaction should have failed").err;
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(0, InstructionError::Custom(error.into()))
+    );
+}
+
+#[test]
+fn initialize_records_bumps_and_owner() {
+    let mut h = Harness::new();
+    h.initialize(0, None).unwrap();
+
+    let state = h.state();
+    let (_, state_bump) = Pubkey::find_program_address(&[b"state", h.owner.pubkey().as_ref()], &vault::ID);
+    let (_, vault_bump) = Pubkey::find_program_address(&[b"vault", h.vault_state().as_ref()], &vault::ID);
+    assert_eq!(state.state_bump, state_bump);
+    assert_eq!(state.vault_bump, vault_bump);
+    assert_eq!(state.owner, h.owner.pubkey());
+    assert_eq!(state.creator, h.owner.pubkey());
+}
+
+#[test]
+fn deposit_withdraw_close_round_trip() {
+    let mut h = Harness::new();
+    h.initialize(0, None).unwrap();
+
+    h.deposit(LAMPORTS_PER_SOL).unwrap();
+    assert_eq!(h.balance(&h.vault()), LAMPORTS_PER_SOL);
+    assert_eq!(h.state().total_deposited, LAMPORTS_PER_SOL);
+
+    h.withdraw(LAMPORTS_PER_SOL / 2).unwrap();
+    assert_eq!(h.balance(&h.vault()), LAMPORTS_PER_SOL / 2);
+    assert_eq!(h.state().total_withdrawn, LAMPORTS_PER_SOL / 2);
+
+    let before = h.balance(&h.owner.pubkey());
+    h.close().unwrap();
+    assert_eq!(h.balance(&h.vault()), 0);
+    assert_eq!(h.balance(&h.vault_state()), 0);
+    assert!(h.balance(&h.owner.pubkey()) > before);
+}
+
+#[test]
+fn initialize_rejects_wrong_vault_seeds() {
+    let mut h = Harness::new();
+    let ix = Instruction {
+        program_id: vault::ID,
+        accounts: vault::accounts::Initialize {
+            signer: h.owner.pubkey(),
+            vault_state: h.vault_state(),
+            vault: Keypair::new().pubkey(),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: vault::instruction::Initialize { unlock_timestamp: 0, vesting: None }.data(),
+    };
+    let owner = h.owner.insecure_clone();
+    assert_anchor_error(h.send(ix, &owner), anchor_lang::error::ErrorCode::ConstraintSeeds);
+}
+
+#[test]
+fn withdraw_rejects_foreign_signer() {
+    let mut h = Harness::new();
+    h.initialize(0, None).unwrap();
+    h.deposit(LAMPORTS_PER_SOL).unwrap();
+
+    let thief = Keypair::new();
+    h.svm.airdrop(&thief.pubkey(), LAMPORTS_PER_SOL).unwrap();
+    let (vault_state, vault) = (h.vault_state(), h.vault());
+    assert_vault_error(
+        h.withdraw_with(&thief, vault_state, vault, 1_000),
+        VaultError::Unauthorized,
+    );
+}
+
+#[test]
+fn withdraw_rejects_wrong_vault_account() {
+    let mut h = Harness::new();
+    h.initialize(0, None).unwrap();
+    h.deposit(LAMPORTS_PER_SOL).unwrap();
+
+    let owner = h.owner.insecure_clone();
+    let vault_state = h.vault_state();
+    assert_anchor_error(
+        h.withdraw_with(&owner, vault_state, Keypair::new().pubkey(), 1_000),
+        anchor_lang::error::ErrorCode::ConstraintSeeds,
+    );
+}
+
+#[test]
+fn deposit_rejects_zero_amount() {
+    let mut h = Harness::new();
+    h.initialize(0, None).unwrap();
+    assert_vault_error(h.deposit(0), VaultError::ZeroAmount);
+}
+
+#[test]
+fn withdraw_rejects_overdraft_and_rent_violation() {
+    let mut h = Harness::new();
+    h.initialize(0, None).unwrap();
+    h.deposit(LAMPORTS_PER_SOL).unwrap();
+
+    assert_vault_error(h.withdraw(2 * LAMPORTS_PER_SOL), VaultError::InsufficientFunds);
+    assert_vault_error(h.withdraw(LAMPORTS_PER_SOL), VaultError::RentExemptViolation);
+    assert_vault_error(h.withdraw(0), VaultError::ZeroAmount);
+}
+
+#[test]
+fn time_lock_blocks_withdraw_and_close_until_unlock() {
+    let mut h = Harness::new();
+    h.warp_to(1_000);
+    h.initialize(2_000, None).unwrap();
+    h.deposit(LAMPORTS_PER_SOL).unwrap();
+
+    assert_vault_error(h.withdraw(1_000), VaultError::FundsLocked);
+    assert_vault_error(h.close(), VaultError::FundsLocked);
+
+    h.warp_to(2_000);
+    h.withdraw(1_000).unwrap();
+    h.close().unwrap();
+}
+
+#[test]
+fn vesting_releases_deposits_linearly() {
+    let mut h = Harness::new();
+    h.warp_to(0);
+    let vesting = VestingSchedule { start: 0, cliff: 100, end: 1_000 };
+    h.initialize(0, Some(vesting)).unwrap();
+    h.deposit(LAMPORTS_PER_SOL).unwrap();
+
+    h.warp_to(50);
+    assert_vault_error(h.withdraw(1_000), VaultError::FundsLocked);
+
+    h.warp_to(500);
+    assert_vault_error(h.withdraw(LAMPORTS_PER_SOL / 2 + 1), VaultError::FundsLocked);
+    h.withdraw(LAMPORTS_PER_SOL / 2).unwrap();
+
+    h.warp_to(1_000);
+    h.close().unwrap();
+}
+
+#[test]
+fn initialize_rejects_invalid_vesting() {
+    let mut h = Harness::new();
+    let vesting = VestingSchedule { start: 100, cliff: 50, end: 1_000 };
+    assert_vault_error(h.initialize(0, Some(vesting)), VaultError::InvalidVestingSchedule);
+}
+
+#[test]
+fn cancel_stream_on_locked_vault_closes_it_without_paying() {
+    let mut h = Harness::new();
+    h.warp_to(1_000);
+    h.initialize(5_000, None).unwrap();
+    h.deposit(LAMPORTS_PER_SOL).unwrap();
+
+    let recipient = Keypair::new().pubkey();
+    h.svm.airdrop(&recipient, LAMPORTS_PER_SOL).unwrap();
+    h.create_stream(&recipient, 1_000, 1_000, 2_000).unwrap();
+    assert_eq!(h.state().open_delegations, 1);
+
+    h.warp_to(1_500);
+    h.cancel_stream(&recipient).unwrap();
+    assert_eq!(h.balance(&recipient), LAMPORTS_PER_SOL);
+    assert_eq!(h.balance(&h.stream(&recipient, 0)), 0);
+    assert_eq!(h.state().open_delegations, 0);
+
+    h.warp_to(5_000);
+    h.close().unwrap();
+}
+
+#[test]
+fn cancel_stream_on_underfunded_vault_pays_what_is_available() {
+    let mut h = Harness::new();
+    h.warp_to(0);
+    h.initialize(0, None).unwrap();
+    h.deposit(LAMPORTS_PER_SOL).unwrap();
+
+    let recipient = Keypair::new().pubkey();
+    h.svm.airdrop(&recipient, LAMPORTS_PER_SOL).unwrap();
+    let rate = LAMPORTS_PER_SOL / 100;
+    h.create_stream(&recipient, rate, 0, 1_000).unwrap();
+
+    // 5 SOL accrued against a vault holding 1
+    h.warp_to(500);
+    h.cancel_stream(&recipient).unwrap();
+
+    let rent_exempt = h.svm.minimum_balance_for_rent_exemption(0);
+    let paid = (LAMPORTS_PER_SOL - rent_exempt) / rate * rate;
+    assert_eq!(h.balance(&recipient), LAMPORTS_PER_SOL + paid);
+    assert_eq!(h.balance(&h.vault()), LAMPORTS_PER_SOL - paid);
+    assert_eq!(h.state().open_delegations, 0);
+}
+
+#[test]
+fn owner_replaces_guardian_of_frozen_vault_after_timelock() {
+    let mut h = Harness::new();
+    h.warp_to(0);
+    h.initialize(0, None).unwrap();
+    h.deposit(LAMPORTS_PER_SOL).unwrap();
+
+    let guardian = Keypair::new();
+    h.svm.airdrop(&guardian.pubkey(), LAMPORTS_PER_SOL).unwrap();
+    h.owner_action(
+        vault::instruction::SetGuardian {
+            guardian: Some(guardian.pubkey()),
+            recovery_timelock: MIN_RECOVERY_TIMELOCK,
+        }
+        .data(),
+    )
+    .unwrap();
+    h.guardian_action(&guardian, vault::instruction::Freeze {}.data()).unwrap();
+    assert_vault_error(h.withdraw(1_000), VaultError::VaultFrozen);
+
+    // The guardian goes silent, the owner starts swapping it out
+    h.owner_action(vault::instruction::SetGuardian { guardian: None, recovery_timelock: 0 }.data())
+        .unwrap();
+    assert!(h.state().frozen);
+    assert_eq!(h.state().guardian, Some(guardian.pubkey()));
+    assert_vault_error(
+        h.owner_action(vault::instruction::CompleteGuardianChange {}.data()),
+        VaultError::GuardianChangeTimelockNotPassed,
+    );
+
+    h.warp_to(MIN_RECOVERY_TIMELOCK);
+    h.owner_action(vault::instruction::CompleteGuardianChange {}.data()).unwrap();
+    assert!(!h.state().frozen);
+    assert_eq!(h.state().guardian, None);
+    assert_vault_error(
+        h.guardian_action(&guardian, vault::instruction::Freeze {}.data()),
+        VaultError::NotGuardian,
+    );
+    h.withdraw(1_000).unwrap();
+}
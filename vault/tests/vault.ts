@@ -29,9 +29,9 @@ describe("vault", () => {
       program.programId
     );
 
-    // Initialize vault
+    // Initialize vault, unlocked immediately and without vesting
     const tx = await program.methods
-      .initialize()
+      .initialize(new anchor.BN(0), null)
       .accounts({
         signer: wallet.publicKey,
         vaultState: vaultState,
@@ -110,6 +110,10 @@ describe("vault", () => {
     const postAccount = await provider.connection.getAccountInfo(vault);
     assert.equal(postAccount, null);
 
+    // Verify the state account was closed too
+    const postState = await provider.connection.getAccountInfo(vaultState);
+    assert.equal(postState, null);
+
     // Verify funds were returned
     const signerPostBalance = await provider.connection.getBalance(wallet.publicKey);
     console.log("Returned balance:", preBalance);